@@ -0,0 +1,68 @@
+//! Bond units are held by investors in token accounts frozen by the treasury bonds (the
+//! freeze authority of the bond mint), so that they only change hands through the program
+//! and the investors' holdings stay in step with their token balances. The program thaws
+//! an investor's token account for the duration of its own mints, transfers and burns.
+
+use {
+    crate::state::treasury_bonds::TreasuryBonds,
+    anchor_lang::prelude::*,
+    anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface},
+};
+
+// thaw the investor's token account of the bond units, if frozen
+pub fn thaw_bond_tokens<'info>(
+    treasury_bonds: &Account<'info, TreasuryBonds>,
+    bond_mint: &InterfaceAccount<'info, Mint>,
+    bond_tokens: &InterfaceAccount<'info, TokenAccount>,
+    bond_token_program: &Interface<'info, TokenInterface>,
+) -> Result<()> {
+    if !bond_tokens.is_frozen() {
+        return Ok(());
+    }
+
+    let seeds = &[
+        b"treasury-bonds",
+        treasury_bonds.owner.as_ref(),
+        treasury_bonds.issue_no.as_bytes(),
+        &[treasury_bonds.bump],
+    ];
+
+    let signer = &[&seeds[..]];
+
+    token_interface::thaw_account(CpiContext::new_with_signer(
+        bond_token_program.to_account_info(),
+        token_interface::ThawAccount {
+            account: bond_tokens.to_account_info(),
+            mint: bond_mint.to_account_info(),
+            authority: treasury_bonds.to_account_info(),
+        },
+        signer,
+    ))
+}
+
+// freeze the investor's token account of the bond units once the program is done with it
+pub fn freeze_bond_tokens<'info>(
+    treasury_bonds: &Account<'info, TreasuryBonds>,
+    bond_mint: &InterfaceAccount<'info, Mint>,
+    bond_tokens: &InterfaceAccount<'info, TokenAccount>,
+    bond_token_program: &Interface<'info, TokenInterface>,
+) -> Result<()> {
+    let seeds = &[
+        b"treasury-bonds",
+        treasury_bonds.owner.as_ref(),
+        treasury_bonds.issue_no.as_bytes(),
+        &[treasury_bonds.bump],
+    ];
+
+    let signer = &[&seeds[..]];
+
+    token_interface::freeze_account(CpiContext::new_with_signer(
+        bond_token_program.to_account_info(),
+        token_interface::FreezeAccount {
+            account: bond_tokens.to_account_info(),
+            mint: bond_mint.to_account_info(),
+            authority: treasury_bonds.to_account_info(),
+        },
+        signer,
+    ))
+}
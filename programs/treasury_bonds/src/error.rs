@@ -13,10 +13,10 @@ pub enum TreasuryBondsError {
     InvalidBondTenor,
     #[msg("Invalid bond coupon rate")]
     InvalidBondCouponRate,
-    #[msg("Invalid value date length")]
-    InvalidValueDateLength,
-    #[msg("Invalid redemption date length")]
-    InvalidValueRedemptionLength,
+    #[msg("Invalid value date")]
+    InvalidValueDate,
+    #[msg("Invalid redemption date")]
+    InvalidRedemptionDate,
     #[msg("Invalid amount.")]
     InvalidAmount,
     #[msg("Available balance should match tranfer amount.")]
@@ -27,6 +27,78 @@ pub enum TreasuryBondsError {
     InvalidMinimumBidAmount,
     #[msg("Invalid bond maturity status.")]
     InvalidBondMaturityStatus,
+    #[msg("Amount exceeds the remaining amount offered.")]
+    AmountOfferedExceeded,
+    #[msg("Amount is below the minimum trade amount.")]
+    InvalidTradeAmount,
+    #[msg("Treasury bonds cannot be redeemed before maturity.")]
+    EarlyRedemptionNotAllowed,
+    #[msg("Invalid treasury vault token account.")]
+    InvalidVaultAccount,
+    #[msg("Invalid bond mint.")]
+    InvalidBondMint,
+    #[msg("Transfer hooks require the Token-2022 program.")]
+    InvalidBondTokenProgram,
+    #[msg("Bond units can only be transferred through the program.")]
+    TransferNotAllowed,
+    #[msg("Invalid day-count convention.")]
+    InvalidDayCount,
+
+    // settlement
+    #[msg("Mint is not an accepted settlement mint.")]
+    SettlementMintNotAllowed,
+    #[msg("Settlement mint is already listed.")]
+    SettlementMintAlreadyListed,
+    #[msg("Maximum number of settlement mints reached.")]
+    SettlementMintsExceeded,
+    #[msg("Token account mint does not match the settlement mint.")]
+    InvalidSettlementMint,
+    #[msg("Invalid token account owner.")]
+    InvalidTokenAccountOwner,
+
+    // pricing
+    #[msg("Either a clean price or a yield should be quoted.")]
+    InvalidPriceQuote,
+    #[msg("Price or yield could not be solved.")]
+    PriceYieldNotSolved,
+
+    // coupon
+    #[msg("No coupon is due for payment.")]
+    NoCouponDue,
+    #[msg("Zero coupon bonds do not pay coupons.")]
+    CouponNotApplicable,
+    #[msg("Coupons that have fallen due should be paid first.")]
+    CouponPaymentDue,
+
+    // principal
+    #[msg("Invalid amortization schedule.")]
+    InvalidAmortizationSchedule,
+    #[msg("No principal is due for repayment.")]
+    NoPrincipalDue,
+    #[msg("Principal tranches that have fallen due should be repaid first.")]
+    PrincipalRepaymentDue,
+
+    // tax
+    #[msg("Invalid withholding tax rate.")]
+    InvalidWithholdingTaxRate,
+    #[msg("Invalid tax authority token account.")]
+    InvalidTaxAuthorityAccount,
+
+    // auction
+    #[msg("Invalid auction dates.")]
+    InvalidAuctionDates,
+    #[msg("Auction is not accepting bids.")]
+    AuctionNotOpen,
+    #[msg("Auction bid window has not closed.")]
+    AuctionNotClosed,
+    #[msg("Auction is already closed.")]
+    AuctionAlreadyClosed,
+    #[msg("Invalid bid yield.")]
+    InvalidBidYield,
+    #[msg("Maximum number of bid yields reached.")]
+    YieldLevelsExceeded,
+    #[msg("Bid is below the minimum amount of a new bid yield.")]
+    InvalidYieldLevelAmount,
 
     //
     #[msg("Invalid country length")]
@@ -43,10 +115,78 @@ pub enum TreasuryBondsError {
     InvalidInvestorStatus,
     #[msg("Insufficient funds.")]
     InsufficientFunds,
+    #[msg("Buyer and seller should be different investors.")]
+    SelfTrade,
+
+    // offer
+    #[msg("Offer has expired.")]
+    OfferExpired,
+    #[msg("Invalid offer expiry date.")]
+    InvalidOfferExpiry,
+    #[msg("Invalid offer price.")]
+    InvalidOfferPrice,
+    #[msg("Amount exceeds the amount offered for sale.")]
+    OfferAmountExceeded,
+
+    // order book
+    #[msg("Invalid order side.")]
+    InvalidOrderSide,
+    #[msg("Invalid order price.")]
+    InvalidOrderPrice,
+    #[msg("Order book is full and the order is not priced better than its worst order.")]
+    OrderBookFull,
+    #[msg("Order is not the best order on its side of the book.")]
+    OrderNotAtTopOfBook,
+    #[msg("Bid price is below the ask price.")]
+    OrdersNotCrossed,
+    #[msg("Order can still be matched.")]
+    OrderStillMatchable,
+
+    // roles
+    #[msg("Invalid role.")]
+    InvalidRole,
+    #[msg("Role is already granted to the account.")]
+    RoleAlreadyGranted,
+    #[msg("Role is not granted to the account.")]
+    RoleNotGranted,
+    #[msg("Maximum number of role grants reached.")]
+    RoleGrantsExceeded,
+    #[msg("Signer does not hold the role required for the instruction.")]
+    MissingRole,
+
+    // proposals
+    #[msg("Invalid approvers or approval threshold.")]
+    InvalidApprovers,
+    #[msg("Signer is not an approver.")]
+    NotAnApprover,
+    #[msg("Invalid proposal action.")]
+    InvalidProposalAction,
+    #[msg("Approver has already approved the proposal.")]
+    ProposalAlreadyApproved,
+    #[msg("Proposal has expired.")]
+    ProposalExpired,
+    #[msg("Proposal has not met the approval threshold.")]
+    ProposalNotApproved,
+    #[msg("Proposal is already executed.")]
+    ProposalAlreadyExecuted,
+    #[msg("Proposal does not authorize the action.")]
+    ProposalMismatch,
 
     // account
+    #[msg("Signer is not the configs admin.")]
+    InvalidAdmin,
+    #[msg("Signer is not the proposed configs admin.")]
+    InvalidPendingAdmin,
+    #[msg("Signer is not the upgrade authority of the program.")]
+    InvalidUpgradeAuthority,
+    #[msg("Invalid program data account.")]
+    InvalidProgramData,
     #[msg("Account is not initialized.")]
     AccountNotInitialized,
     #[msg("Account is already initialized.")]
     AccountAlreadyInitialized,
+    #[msg("Account is already migrated.")]
+    AccountAlreadyMigrated,
+    #[msg("Invalid account data.")]
+    InvalidAccountData,
 }
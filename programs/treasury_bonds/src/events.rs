@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct TreasuryBondsPurchased {
+    pub treasury_bonds: Pubkey,  // treasury bonds purchased
+    pub investor: Pubkey,        // investor purchasing the treasury bonds
+    pub amount: u32,             // amount of treasury bonds purchased
+    pub remaining_capacity: u32, // amount of treasury bonds still on offer
+}
+
+#[event]
+pub struct TreasuryBondsSold {
+    pub treasury_bonds: Pubkey,  // treasury bonds sold
+    pub seller_investor: Pubkey, // investor selling the treasury bonds
+    pub buyer_investor: Pubkey,  // investor buying the treasury bonds
+    pub amount: u32,             // amount of treasury bonds sold
+    pub clean_price: u32,        // agreed clean price per 100 face value (2 decimals)
+    pub dirty_price: u32,        // clean price plus accrued coupon per 100 face value (2 decimals)
+    pub accrued_coupon: u64,     // coupon accrued since the last coupon date (in smallest unit)
+    pub settlement_amount: u64,  // amount paid by the buyer (in smallest unit)
+}
+
+#[event]
+pub struct AuctionClosed {
+    pub treasury_bonds: Pubkey,     // treasury bonds offered in the auction
+    pub cut_off_yield: Option<u16>, // highest yield accepted (basis points)
+    pub allotted_amount: u32,       // amount of treasury bonds allotted to bids
+    pub remaining_capacity: u32,    // amount of treasury bonds still on offer
+}
+
+#[event]
+pub struct OrdersMatched {
+    pub treasury_bonds: Pubkey, // treasury bonds traded
+    pub bid_order_id: u64,      // id of the bid (buy) order
+    pub ask_order_id: u64,      // id of the ask (sell) order
+    pub clean_price: u32,       // clean price of the trade i.e price of the earlier order
+    pub amount: u32,            // amount of treasury bonds traded
+    pub accrued_coupon: u64,    // coupon accrued since the last coupon date (in smallest unit)
+    pub settlement_amount: u64, // amount paid by the buyer (in smallest unit)
+}
+
+#[event]
+pub struct OrderEvicted {
+    pub treasury_bonds: Pubkey, // treasury bonds traded
+    pub order_id: u64,          // id of the order taken off the book before it was filled
+    pub side: u8,               // bid (buy) or ask (sell) order
+}
+
+#[event]
+pub struct TaxYearClosed {
+    pub investor: Pubkey,        // investor the coupons were paid to
+    pub tax_year: u16,           // calendar year the coupons were paid in
+    pub total_coupons: u64,      // total coupons before withholding tax (in smallest unit)
+    pub total_tax_withheld: u64, // total withholding tax deducted from coupons (in smallest unit)
+}
@@ -1,17 +1,60 @@
 // admin instructions
+pub mod accept_admin;
+pub mod add_settlement_mint;
+pub mod approve_proposal;
+pub mod close_auction;
+pub mod create_proposal;
+pub mod declare_maturity;
+pub mod evict_order;
+pub mod fund_vault;
+pub mod grant_role;
 pub mod init;
+pub mod migrate_coupon_rate;
+pub mod migrate_investor;
+pub mod migrate_treasury_bonds;
+pub mod open_auction;
+pub mod open_order_book;
 pub mod register_treasury_bonds;
+pub mod remove_settlement_mint;
+pub mod revoke_role;
+pub mod set_approvers;
+pub mod set_investor_status;
+pub mod set_tax_profile;
+pub mod transfer_admin;
+pub mod update_tax_profile;
+pub mod withdraw_vault;
 
 // public instructions
 pub mod buy_treasury_bonds;
+pub mod cancel_offer;
+pub mod cancel_order;
+pub mod create_offer;
 pub mod create_token;
+pub mod match_orders;
+pub mod pay_coupon;
+pub mod place_order;
+pub mod quote_price_yield;
 pub mod redeem_treasury_bonds;
 pub mod register_investor;
+pub mod repay_principal;
 pub mod sell_treasury_bonds;
+pub mod settle_bid;
+pub mod submit_bid;
 pub mod transfer_token;
 
+// transfer hook
+pub mod transfer_hook;
+
 // bring everything in scope
 pub use {
-    buy_treasury_bonds::*, create_token::*, init::*, redeem_treasury_bonds::*,
-    register_investor::*, register_treasury_bonds::*, sell_treasury_bonds::*, transfer_token::*,
+    accept_admin::*, add_settlement_mint::*, approve_proposal::*, buy_treasury_bonds::*,
+    cancel_offer::*, cancel_order::*, close_auction::*, create_offer::*, create_proposal::*,
+    create_token::*, declare_maturity::*, evict_order::*, fund_vault::*, grant_role::*, init::*,
+    match_orders::*, migrate_coupon_rate::*, migrate_investor::*, migrate_treasury_bonds::*,
+    open_auction::*, open_order_book::*, pay_coupon::*, place_order::*, quote_price_yield::*,
+    redeem_treasury_bonds::*, register_investor::*, register_treasury_bonds::*,
+    remove_settlement_mint::*, repay_principal::*, revoke_role::*, sell_treasury_bonds::*,
+    set_approvers::*, set_investor_status::*, set_tax_profile::*, settle_bid::*, submit_bid::*,
+    transfer_admin::*, transfer_hook::*, transfer_token::*, update_tax_profile::*,
+    withdraw_vault::*,
 };
@@ -0,0 +1,30 @@
+//! AcceptAdmin instruction handler
+
+use {
+    crate::{error::TreasuryBondsError, state::configs::TreasuryBondsConfigs},
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(mut,
+        constraint = treasury_bonds_configs.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        constraint = treasury_bonds_configs.pending_admin == Some(owner.key()) @ TreasuryBondsError::InvalidPendingAdmin,
+        seeds = [b"treasury-bonds-configs"],
+        bump
+    )]
+    pub treasury_bonds_configs: Account<'info, TreasuryBondsConfigs>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+// second step of the admin handover, signed by the proposed admin
+pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let treasury_bonds_configs = &mut ctx.accounts.treasury_bonds_configs;
+
+    treasury_bonds_configs.admin = ctx.accounts.owner.key();
+    treasury_bonds_configs.pending_admin = None;
+
+    Ok(())
+}
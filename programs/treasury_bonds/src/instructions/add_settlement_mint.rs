@@ -0,0 +1,44 @@
+//! AddSettlementMint instruction handler
+
+use {
+    crate::{
+        error::TreasuryBondsError,
+        state::configs::{TreasuryBondsConfigs, MAX_SETTLEMENT_MINTS},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::Mint,
+};
+
+#[derive(Accounts)]
+pub struct AddSettlementMint<'info> {
+    #[account(mut,
+        constraint = treasury_bonds_configs.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        constraint = treasury_bonds_configs.admin == owner.key() @ TreasuryBondsError::InvalidAdmin,
+        seeds = [b"treasury-bonds-configs"],
+        bump
+    )]
+    pub treasury_bonds_configs: Account<'info, TreasuryBondsConfigs>,
+    pub mint_token: Account<'info, Mint>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+pub fn add_settlement_mint(ctx: Context<AddSettlementMint>) -> Result<()> {
+    let treasury_bonds_configs = &mut ctx.accounts.treasury_bonds_configs;
+    let mint_token = &ctx.accounts.mint_token;
+
+    if treasury_bonds_configs.is_settlement_mint(&mint_token.key()) {
+        return Err(TreasuryBondsError::SettlementMintAlreadyListed.into());
+    }
+
+    if treasury_bonds_configs.settlement_mints.len() >= MAX_SETTLEMENT_MINTS {
+        return Err(TreasuryBondsError::SettlementMintsExceeded.into());
+    }
+
+    treasury_bonds_configs
+        .settlement_mints
+        .push(mint_token.key());
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+//! ApproveProposal instruction handler
+
+use {
+    crate::{
+        error::TreasuryBondsError,
+        state::{
+            configs::TreasuryBondsConfigs,
+            proposal::{Proposal, MAX_APPROVERS},
+        },
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct ApproveProposal<'info> {
+    #[account(
+        constraint = treasury_bonds_configs.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        constraint = treasury_bonds_configs.is_approver(&owner.key()) @ TreasuryBondsError::NotAnApprover,
+        seeds = [b"treasury-bonds-configs"],
+        bump
+    )]
+    pub treasury_bonds_configs: Box<Account<'info, TreasuryBondsConfigs>>,
+    #[account(mut,
+        constraint = !proposal.is_executed @ TreasuryBondsError::ProposalAlreadyExecuted,
+        seeds = [b"proposal", proposal.proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+    pub owner: Signer<'info>,
+}
+
+pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let owner = &ctx.accounts.owner;
+
+    // proposal should still be open for approval
+    let now = Clock::get()?.unix_timestamp;
+    if proposal.has_expired(now) {
+        return Err(TreasuryBondsError::ProposalExpired.into());
+    }
+
+    if proposal.approvals.contains(&owner.key()) {
+        return Err(TreasuryBondsError::ProposalAlreadyApproved.into());
+    }
+
+    // approvals of replaced approvers are dropped to make room
+    if proposal.approvals.len() >= MAX_APPROVERS {
+        let treasury_bonds_configs = &ctx.accounts.treasury_bonds_configs;
+        proposal
+            .approvals
+            .retain(|approver| treasury_bonds_configs.is_approver(approver));
+    }
+
+    proposal.approvals.push(owner.key());
+
+    Ok(())
+}
@@ -2,13 +2,19 @@
 
 use {
     crate::{
+        bond_units::{freeze_bond_tokens, thaw_bond_tokens},
         error::TreasuryBondsError,
-        state::{investor::Investor, treasury_bonds::TreasuryBonds},
+        events::TreasuryBondsPurchased,
+        state::{
+            amortization_schedule::AmortizationSchedule, coupon_schedule::CouponSchedule,
+            holding::Holding, investor::Investor, treasury_bonds::TreasuryBonds,
+        },
     },
     anchor_lang::prelude::*,
     anchor_spl::{
         associated_token::AssociatedToken,
         token::{transfer, Mint, Token, TokenAccount, Transfer},
+        token_interface::{self, TokenInterface},
     },
 };
 
@@ -16,24 +22,72 @@ use {
 #[instruction(params: BuyTreasuryBondsParams)]
 pub struct BuyTreasuryBonds<'info> {
     #[account(mut,
-        constraint = treasury_bonds.is_initialized @ TreasuryBondsError::AccountNotInitialized
+        constraint = treasury_bonds.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [
+            b"treasury-bonds",
+            treasury_bonds.owner.as_ref(),
+            treasury_bonds.issue_no.as_bytes()
+        ],
+        bump = treasury_bonds.bump
     )]
     pub treasury_bonds: Account<'info, TreasuryBonds>,
-    #[account(mut,has_one = owner,
+    #[account(has_one = treasury_bonds,
+        constraint = coupon_schedule.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [b"coupon-schedule", treasury_bonds.key().as_ref()],
+        bump
+    )]
+    pub coupon_schedule: Account<'info, CouponSchedule>,
+    #[account(has_one = treasury_bonds,
+        constraint = amortization_schedule.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [b"amortization-schedule", treasury_bonds.key().as_ref()],
+        bump
+    )]
+    pub amortization_schedule: Box<Account<'info, AmortizationSchedule>>,
+    #[account(has_one = owner,
         constraint = investor.active @ TreasuryBondsError::InvalidInvestorStatus
     )]
     pub investor: Account<'info, Investor>,
-    #[account(mut)]
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + Holding::INIT_SPACE,
+        seeds = [b"holding", investor.key().as_ref(), treasury_bonds.key().as_ref()],
+        bump
+    )]
+    pub holding: Account<'info, Holding>,
+    #[account(mut,
+        constraint = sender_tokens.mint == mint_token.key() @ TreasuryBondsError::InvalidSettlementMint,
+        constraint = sender_tokens.owner == owner.key() @ TreasuryBondsError::InvalidTokenAccountOwner
+    )]
     pub sender_tokens: Account<'info, TokenAccount>,
-    #[account(mut)]
+    // purchase proceeds are paid into the treasury vault
+    #[account(mut,
+        constraint = recipient_tokens.key() == treasury_bonds.vault_tokens @ TreasuryBondsError::InvalidVaultAccount
+    )]
     pub recipient_tokens: Account<'info, TokenAccount>,
-    #[account(mut)]
+    #[account(mut,
+        address = treasury_bonds.settlement_mint @ TreasuryBondsError::InvalidSettlementMint
+    )]
     pub mint_token: Account<'info, Mint>,
+    #[account(mut,
+        address = treasury_bonds.bond_mint @ TreasuryBondsError::InvalidBondMint
+    )]
+    pub bond_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    // bond units are minted to the investor's token account
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = bond_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = bond_token_program
+    )]
+    pub bond_tokens: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
     // mut makes it changeble (mutable)
     #[account(mut)]
     pub owner: Signer<'info>,
     pub token_program: Program<'info, Token>,
-    pub associate_token_program: Program<'info, AssociatedToken>,
+    pub bond_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
@@ -55,33 +109,74 @@ pub fn buy_treasury_bonds(
     let sender_tokens = &ctx.accounts.sender_tokens;
     let recipient_tokens = &ctx.accounts.recipient_tokens;
     let token_program = &ctx.accounts.token_program;
+    let bond_mint = &ctx.accounts.bond_mint;
+    let bond_tokens = &ctx.accounts.bond_tokens;
+    let bond_token_program = &ctx.accounts.bond_token_program;
     let treasury_bonds = &mut ctx.accounts.treasury_bonds;
-    let investor = &mut ctx.accounts.investor;
+    let investor = &ctx.accounts.investor;
+    let holding = &mut ctx.accounts.holding;
+    let coupon_schedule = &ctx.accounts.coupon_schedule;
+    let amortization_schedule = &ctx.accounts.amortization_schedule;
     let unit_cost_of_treasury_bonds: u32 = treasury_bonds.unit_cost_of_treasury_bonds;
+    let total_amounts_offered = treasury_bonds.total_amounts_offered;
     let total_amounts_accepted = treasury_bonds.total_amounts_accepted;
     let total_available_funds = treasury_bonds.total_available_funds;
     let minimum_bid_amount = treasury_bonds.minimum_bid_amount;
-    let total_units_treasury_bonds: u32 = investor.total_units_treasury_bonds;
-    let available_funds: u32 = investor.available_funds;
-    let decimals = treasury_bonds.decimals as u64;
+    let total_units_treasury_bonds: u32 = holding.total_units_treasury_bonds;
+    let available_funds: u32 = holding.available_funds;
     let _amount = params.amount;
 
+    // treasury bonds should not have matured
+    let now = Clock::get()?.unix_timestamp;
+    if treasury_bonds.has_matured(now) {
+        return Err(TreasuryBondsError::InvalidBondMaturityStatus.into());
+    }
+
     if _amount < minimum_bid_amount {
         return Err(TreasuryBondsError::InvalidMinimumBidAmount.into());
     }
 
+    // purchases are capped at the total amounts offered for the given bond
+    let remaining_capacity = total_amounts_offered.saturating_sub(total_amounts_accepted);
+    if _amount > remaining_capacity {
+        return Err(TreasuryBondsError::AmountOfferedExceeded.into());
+    }
+
     // Get unit_treasury_bonds from the product of unit_cost_of_treasury_bonds and _amount
     let unit_treasury_bonds = unit_cost_of_treasury_bonds
         .checked_mul(_amount)
         .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
 
+    // holding is created on the investor's first purchase of the treasury bonds
+    if !holding.is_initialized {
+        holding.investor = investor.key();
+        holding.treasury_bonds = treasury_bonds.key();
+        holding.is_initialized = true;
+        holding.bump = ctx.bumps.holding;
+    }
+
+    // bondholders only earn coupons and principal falling due after the purchase
+    let periods_due = coupon_schedule.periods_due(now);
+    let tranches_due = amortization_schedule.tranches_due(now);
+    holding.verify_settled(periods_due, tranches_due)?;
+    holding.coupon_periods_paid = periods_due;
+    holding.principal_tranches_repaid = tranches_due;
+
+    // A new bondholder is added to the holders count
+    if available_funds == 0 {
+        treasury_bonds.holders_count = treasury_bonds
+            .holders_count
+            .checked_add(1)
+            .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+    }
+
     // Increment total_units_treasury_bonds with new unit_treasury_bonds
-    investor.total_units_treasury_bonds = total_units_treasury_bonds
+    holding.total_units_treasury_bonds = total_units_treasury_bonds
         .checked_add(unit_treasury_bonds)
         .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
 
     // Increment available_funds with new _amount
-    investor.available_funds = available_funds
+    holding.available_funds = available_funds
         .checked_add(_amount)
         .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
 
@@ -95,17 +190,25 @@ pub fn buy_treasury_bonds(
         .checked_add(_amount)
         .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
 
-    let base: u32 = 10;
-    let exponent = treasury_bonds.decimals as u32;
+    let _amount = treasury_bonds
+        .face_value_of(_amount)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    // amortizing bonds are bought on the face value still outstanding
+    let _amount =
+        AmortizationSchedule::share_of(_amount, amortization_schedule.outstanding_rate_at(now))
+            .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
 
-    // lets get the amount in decimal format
-    // 10 ** 9 * 3(base 10, 9 decimals, 3 amount), // 3 amount of token to transfer (in smallest unit i.e 9 decimals)
-    let result = (base).pow(exponent);
-    let _amount = (_amount as u64)
-        .checked_mul(result as u64)
+    // zero coupon bonds are bought at a discount from face value
+    let _amount = treasury_bonds
+        .purchase_price(_amount, now)
         .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
 
-    treasury_bonds.investors.push(*sender.key);
+    // Increment cost_basis with the price paid
+    holding.cost_basis = holding
+        .cost_basis
+        .checked_add(_amount)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
 
     transfer(
         CpiContext::new(
@@ -119,5 +222,41 @@ pub fn buy_treasury_bonds(
         _amount,
     )?;
 
+    let seeds = &[
+        b"treasury-bonds",
+        treasury_bonds.owner.as_ref(),
+        treasury_bonds.issue_no.as_bytes(),
+        &[treasury_bonds.bump],
+    ];
+
+    let signer = &[&seeds[..]];
+
+    // Thaw the investor's bond units for the program to mint to them
+    thaw_bond_tokens(treasury_bonds, bond_mint, bond_tokens, bond_token_program)?;
+
+    // Mint bond units to the investor
+    token_interface::mint_to(
+        CpiContext::new_with_signer(
+            bond_token_program.to_account_info(),
+            token_interface::MintTo {
+                mint: bond_mint.to_account_info(),
+                to: bond_tokens.to_account_info(),
+                authority: treasury_bonds.to_account_info(),
+            },
+            signer,
+        ),
+        unit_treasury_bonds as u64,
+    )?;
+
+    // Freeze the investor's bond units again
+    freeze_bond_tokens(treasury_bonds, bond_mint, bond_tokens, bond_token_program)?;
+
+    emit!(TreasuryBondsPurchased {
+        treasury_bonds: treasury_bonds.key(),
+        investor: holding.investor,
+        amount: params.amount,
+        remaining_capacity: remaining_capacity - params.amount,
+    });
+
     Ok(())
 }
@@ -0,0 +1,80 @@
+//! CancelOffer instruction handler
+
+use {
+    crate::{
+        bond_units::{freeze_bond_tokens, thaw_bond_tokens},
+        error::TreasuryBondsError,
+        state::{offer::Offer, treasury_bonds::TreasuryBonds},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token_interface::{self, TokenInterface},
+};
+
+#[derive(Accounts)]
+pub struct CancelOffer<'info> {
+    #[account(
+        constraint = treasury_bonds.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [
+            b"treasury-bonds",
+            treasury_bonds.owner.as_ref(),
+            treasury_bonds.issue_no.as_bytes()
+        ],
+        bump = treasury_bonds.bump
+    )]
+    pub treasury_bonds: Account<'info, TreasuryBonds>,
+    // offer is closed, returning its rent to the seller
+    #[account(mut, close = owner, has_one = owner, has_one = treasury_bonds,
+        seeds = [b"offer", treasury_bonds.key().as_ref(), offer.seller_investor.as_ref()],
+        bump = offer.bump
+    )]
+    pub offer: Account<'info, Offer>,
+    #[account(
+        address = treasury_bonds.bond_mint @ TreasuryBondsError::InvalidBondMint
+    )]
+    pub bond_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    // offer's delegation of the bond units is revoked
+    #[account(mut,
+        associated_token::mint = bond_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = bond_token_program
+    )]
+    pub bond_tokens: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub bond_token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn cancel_offer(ctx: Context<CancelOffer>) -> Result<()> {
+    let offer = &ctx.accounts.offer;
+    let bond_tokens = &ctx.accounts.bond_tokens;
+
+    // Thaw the investor's bond units for the offer's delegation to be revoked
+    thaw_bond_tokens(
+        &ctx.accounts.treasury_bonds,
+        &ctx.accounts.bond_mint,
+        bond_tokens,
+        &ctx.accounts.bond_token_program,
+    )?;
+
+    // Revoke the offer's delegation, unless the seller has since delegated the bond units elsewhere
+    if bond_tokens.delegate == Some(offer.key()).into() {
+        token_interface::revoke(CpiContext::new(
+            ctx.accounts.bond_token_program.to_account_info(),
+            token_interface::Revoke {
+                source: bond_tokens.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ))?;
+    }
+
+    // Freeze the investor's bond units again
+    freeze_bond_tokens(
+        &ctx.accounts.treasury_bonds,
+        &ctx.accounts.bond_mint,
+        bond_tokens,
+        &ctx.accounts.bond_token_program,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,156 @@
+//! CancelOrder instruction handler
+
+use {
+    crate::{
+        bond_units::{freeze_bond_tokens, thaw_bond_tokens},
+        error::TreasuryBondsError,
+        state::{
+            order::Order,
+            order_book::{OrderBook, BID_ORDER},
+            treasury_bonds::TreasuryBonds,
+        },
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        token::{transfer, Mint, Token, TokenAccount, Transfer},
+        token_2022::spl_token_2022::onchain::invoke_transfer_checked,
+        token_interface::{self, TokenInterface},
+    },
+};
+
+#[derive(Accounts)]
+pub struct CancelOrder<'info> {
+    #[account(
+        constraint = treasury_bonds.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [
+            b"treasury-bonds",
+            treasury_bonds.owner.as_ref(),
+            treasury_bonds.issue_no.as_bytes()
+        ],
+        bump = treasury_bonds.bump
+    )]
+    pub treasury_bonds: Box<Account<'info, TreasuryBonds>>,
+    #[account(mut, has_one = treasury_bonds,
+        seeds = [b"order-book", treasury_bonds.key().as_ref()],
+        bump = order_book.bump
+    )]
+    pub order_book: Box<Account<'info, OrderBook>>,
+    // order is closed, returning its rent to the investor
+    #[account(mut, close = owner, has_one = owner, has_one = order_book,
+        seeds = [b"order", order_book.key().as_ref(), order.order_id.to_le_bytes().as_ref()],
+        bump = order.bump
+    )]
+    pub order: Box<Account<'info, Order>>,
+    // escrowed settlement amount of a bid is refunded to the investor
+    #[account(mut,
+        constraint = refund_tokens.mint == mint_token.key() @ TreasuryBondsError::InvalidSettlementMint,
+        constraint = refund_tokens.owner == owner.key() @ TreasuryBondsError::InvalidTokenAccountOwner
+    )]
+    pub refund_tokens: Box<Account<'info, TokenAccount>>,
+    #[account(mut,
+        associated_token::mint = mint_token,
+        associated_token::authority = order_book
+    )]
+    pub escrow_tokens: Box<Account<'info, TokenAccount>>,
+    #[account(address = treasury_bonds.settlement_mint @ TreasuryBondsError::InvalidSettlementMint)]
+    pub mint_token: Box<Account<'info, Mint>>,
+    #[account(
+        address = treasury_bonds.bond_mint @ TreasuryBondsError::InvalidBondMint
+    )]
+    pub bond_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    // escrowed bond units of an ask are returned to the investor
+    #[account(mut,
+        associated_token::mint = bond_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = bond_token_program
+    )]
+    pub bond_tokens: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    #[account(mut,
+        associated_token::mint = bond_mint,
+        associated_token::authority = order_book,
+        associated_token::token_program = bond_token_program
+    )]
+    pub escrow_bond_tokens: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub bond_token_program: Interface<'info, TokenInterface>,
+}
+
+// cancels a resting or evicted order, returning whatever is left in escrow for it to the investor
+pub fn cancel_order<'info>(ctx: Context<'_, '_, '_, 'info, CancelOrder<'info>>) -> Result<()> {
+    let treasury_bonds = &ctx.accounts.treasury_bonds;
+    let order_book = &mut ctx.accounts.order_book;
+    let order = &ctx.accounts.order;
+
+    // orders evicted from a full side of the book are no longer resting on it
+    order_book.remove_order(order.side, order.order_id);
+
+    let treasury_bonds_key = treasury_bonds.key();
+    let seeds = &[
+        b"order-book",
+        treasury_bonds_key.as_ref(),
+        &[order_book.bump],
+    ];
+
+    let signer = &[&seeds[..]];
+
+    if order.side == BID_ORDER {
+        // Refund the escrowed settlement amount to the investor
+        if order.escrowed_amount > 0 {
+            transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.escrow_tokens.to_account_info(),
+                        to: ctx.accounts.refund_tokens.to_account_info(),
+                        authority: order_book.to_account_info(),
+                    },
+                    signer,
+                ),
+                order.escrowed_amount,
+            )?;
+        }
+    } else {
+        // Get unit_treasury_bonds from the product of unit_cost_of_treasury_bonds and order amount
+        let unit_treasury_bonds = treasury_bonds
+            .unit_cost_of_treasury_bonds
+            .checked_mul(order.amount)
+            .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+        // Thaw the investor's bond units for the program to move them
+        thaw_bond_tokens(
+            &ctx.accounts.treasury_bonds,
+            &ctx.accounts.bond_mint,
+            &ctx.accounts.bond_tokens,
+            &ctx.accounts.bond_token_program,
+        )?;
+
+        // Return the escrowed bond units to the investor
+        // remaining accounts carry the transfer hook accounts of bond mints in Token-2022 mode
+        if unit_treasury_bonds > 0 {
+            invoke_transfer_checked(
+                ctx.accounts.bond_token_program.key,
+                ctx.accounts.escrow_bond_tokens.to_account_info(),
+                ctx.accounts.bond_mint.to_account_info(),
+                ctx.accounts.bond_tokens.to_account_info(),
+                order_book.to_account_info(),
+                ctx.remaining_accounts,
+                unit_treasury_bonds as u64,
+                ctx.accounts.bond_mint.decimals,
+                signer,
+            )?;
+        }
+
+        // Freeze the investor's bond units again
+        freeze_bond_tokens(
+            &ctx.accounts.treasury_bonds,
+            &ctx.accounts.bond_mint,
+            &ctx.accounts.bond_tokens,
+            &ctx.accounts.bond_token_program,
+        )?;
+    }
+
+    Ok(())
+}
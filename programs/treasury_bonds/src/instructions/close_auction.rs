@@ -0,0 +1,113 @@
+//! CloseAuction instruction handler
+
+use {
+    crate::{
+        error::TreasuryBondsError,
+        events::AuctionClosed,
+        state::{
+            amortization_schedule::AmortizationSchedule,
+            auction::{issue_price, Auction},
+            configs::{TreasuryBondsConfigs, ISSUANCE_OPERATOR},
+            coupon_schedule::CouponSchedule,
+            treasury_bonds::TreasuryBonds,
+        },
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct CloseAuction<'info> {
+    #[account(
+        constraint = treasury_bonds_configs.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        constraint = treasury_bonds_configs.has_role(ISSUANCE_OPERATOR, &owner.key()) @ TreasuryBondsError::MissingRole,
+        seeds = [b"treasury-bonds-configs"],
+        bump
+    )]
+    pub treasury_bonds_configs: Box<Account<'info, TreasuryBondsConfigs>>,
+    #[account(mut,
+        constraint = treasury_bonds.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [
+            b"treasury-bonds",
+            treasury_bonds.owner.as_ref(),
+            treasury_bonds.issue_no.as_bytes()
+        ],
+        bump = treasury_bonds.bump
+    )]
+    pub treasury_bonds: Account<'info, TreasuryBonds>,
+    #[account(has_one = treasury_bonds,
+        constraint = coupon_schedule.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [b"coupon-schedule", treasury_bonds.key().as_ref()],
+        bump
+    )]
+    pub coupon_schedule: Account<'info, CouponSchedule>,
+    #[account(has_one = treasury_bonds,
+        constraint = amortization_schedule.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [b"amortization-schedule", treasury_bonds.key().as_ref()],
+        bump
+    )]
+    pub amortization_schedule: Box<Account<'info, AmortizationSchedule>>,
+    #[account(mut, has_one = treasury_bonds,
+        constraint = auction.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        constraint = !auction.is_closed @ TreasuryBondsError::AuctionAlreadyClosed,
+        seeds = [b"auction", treasury_bonds.key().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+// determines the cut-off yield and allots the amount offered to the bids, all allotments
+// being priced at the cut-off yield. each bid is then settled (allotted or refunded from
+// escrow) through settle_bid
+pub fn close_auction(ctx: Context<CloseAuction>) -> Result<()> {
+    let treasury_bonds = &mut ctx.accounts.treasury_bonds;
+    let coupon_schedule = &ctx.accounts.coupon_schedule;
+    let amortization_schedule = &ctx.accounts.amortization_schedule;
+    let auction = &mut ctx.accounts.auction;
+    let total_amounts_offered = treasury_bonds.total_amounts_offered;
+    let total_amounts_accepted = treasury_bonds.total_amounts_accepted;
+
+    // auction can only be closed once the bid window has closed
+    let now = Clock::get()?.unix_timestamp;
+    if now < auction.bid_closing_date {
+        return Err(TreasuryBondsError::AuctionNotClosed.into());
+    }
+
+    // allot against the remaining amount offered for the given bond
+    let remaining_capacity = total_amounts_offered.saturating_sub(total_amounts_accepted);
+    let allotted_amount = auction.allot(remaining_capacity);
+
+    // clearing price at the cut-off yield, allotments are priced at the purchase price
+    // when no competitive bid is accepted
+    auction.clearing_price = match auction.cut_off_yield {
+        Some(cut_off_yield) => Some(
+            issue_price(
+                treasury_bonds,
+                coupon_schedule,
+                amortization_schedule,
+                cut_off_yield,
+            )
+            .ok_or(TreasuryBondsError::PriceYieldNotSolved)?,
+        ),
+        None => None,
+    };
+
+    // Increment total_amounts_accepted with the allotted amount, which the bids
+    // settled through settle_bid add up to exactly
+    treasury_bonds.total_amounts_accepted = total_amounts_accepted
+        .checked_add(allotted_amount)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    auction.is_closed = true;
+
+    emit!(AuctionClosed {
+        treasury_bonds: treasury_bonds.key(),
+        cut_off_yield: auction.cut_off_yield,
+        allotted_amount,
+        remaining_capacity: remaining_capacity - allotted_amount,
+    });
+
+    Ok(())
+}
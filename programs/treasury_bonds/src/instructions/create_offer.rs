@@ -0,0 +1,158 @@
+//! CreateOffer instruction handler
+
+use {
+    crate::{
+        bond_units::{freeze_bond_tokens, thaw_bond_tokens},
+        error::TreasuryBondsError,
+        state::{
+            holding::Holding, investor::Investor, offer::Offer, treasury_bonds::TreasuryBonds,
+        },
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token_interface::{self, TokenInterface},
+};
+
+#[derive(Accounts)]
+#[instruction(params: CreateOfferParams)]
+pub struct CreateOffer<'info> {
+    #[account(
+        constraint = treasury_bonds.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [
+            b"treasury-bonds",
+            treasury_bonds.owner.as_ref(),
+            treasury_bonds.issue_no.as_bytes()
+        ],
+        bump = treasury_bonds.bump
+    )]
+    pub treasury_bonds: Account<'info, TreasuryBonds>,
+    #[account(has_one = owner,
+        constraint = investor.active @ TreasuryBondsError::InvalidInvestorStatus
+    )]
+    pub investor: Account<'info, Investor>,
+    #[account(has_one = investor, has_one = treasury_bonds,
+        seeds = [b"holding", investor.key().as_ref(), treasury_bonds.key().as_ref()],
+        bump = holding.bump
+    )]
+    pub holding: Account<'info, Holding>,
+    // init means to create account
+    // bump to use unique address for account
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Offer::INIT_SPACE,
+        seeds = [b"offer", treasury_bonds.key().as_ref(), investor.key().as_ref()],
+        bump
+    )]
+    pub offer: Account<'info, Offer>,
+    #[account(
+        address = treasury_bonds.bond_mint @ TreasuryBondsError::InvalidBondMint
+    )]
+    pub bond_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    // offer is approved as delegate of the bond units offered
+    #[account(mut,
+        associated_token::mint = bond_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = bond_token_program
+    )]
+    pub bond_tokens: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub bond_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CreateOfferParams {
+    pub amount: u32,      // amount of treasury bonds offered
+    pub clean_price: u32, // clean price per 100 face value (2 decimals i.e 9850 = 98.50)
+    pub expiry_date: i64, // offer can no longer be accepted after this date (unix timestamp)
+}
+
+// seller's offer to sell treasury bonds, accepted by a buyer through sell_treasury_bonds.
+// the offer is approved as delegate of the bond units so that they are delivered against payment
+// without the seller signing the sale.
+pub fn create_offer(ctx: Context<CreateOffer>, params: &CreateOfferParams) -> Result<()> {
+    msg!("Validate inputs");
+    if params.amount == 0 {
+        return Err(TreasuryBondsError::InvalidAmount.into());
+    }
+
+    if params.clean_price == 0 {
+        return Err(TreasuryBondsError::InvalidOfferPrice.into());
+    }
+
+    let treasury_bonds = &ctx.accounts.treasury_bonds;
+    let investor = &ctx.accounts.investor;
+    let holding = &ctx.accounts.holding;
+    let offer = &mut ctx.accounts.offer;
+    let available_funds: u32 = holding.available_funds;
+    let _amount = params.amount;
+
+    // treasury bonds should not have matured
+    let now = Clock::get()?.unix_timestamp;
+    if treasury_bonds.has_matured(now) {
+        return Err(TreasuryBondsError::InvalidBondMaturityStatus.into());
+    }
+
+    // offer should expire before the treasury bonds mature
+    if params.expiry_date <= now || params.expiry_date > treasury_bonds.redemption_date {
+        return Err(TreasuryBondsError::InvalidOfferExpiry.into());
+    }
+
+    // investor's(seller) available funds should cover the amount offered
+    if available_funds < _amount {
+        return Err(TreasuryBondsError::InsufficientFunds.into());
+    }
+
+    // partial sells should meet the minimum trade amount
+    if !treasury_bonds.is_valid_trade_amount(_amount, available_funds) {
+        return Err(TreasuryBondsError::InvalidTradeAmount.into());
+    }
+
+    // Get unit_treasury_bonds from the product of unit_cost_of_treasury_bonds and _amount
+    let unit_treasury_bonds = treasury_bonds
+        .unit_cost_of_treasury_bonds
+        .checked_mul(_amount)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    // offer
+    offer.treasury_bonds = treasury_bonds.key();
+    offer.seller_investor = investor.key();
+    offer.owner = *ctx.accounts.owner.key;
+    offer.amount = _amount;
+    offer.clean_price = params.clean_price;
+    offer.expiry_date = params.expiry_date;
+    offer.bump = ctx.bumps.offer;
+
+    // Thaw the investor's bond units for the offer to be approved as delegate
+    thaw_bond_tokens(
+        treasury_bonds,
+        &ctx.accounts.bond_mint,
+        &ctx.accounts.bond_tokens,
+        &ctx.accounts.bond_token_program,
+    )?;
+
+    // Approve the offer as delegate of the bond units offered
+    token_interface::approve(
+        CpiContext::new(
+            ctx.accounts.bond_token_program.to_account_info(),
+            token_interface::Approve {
+                to: ctx.accounts.bond_tokens.to_account_info(),
+                delegate: offer.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        unit_treasury_bonds as u64,
+    )?;
+
+    // Freeze the investor's bond units again
+    freeze_bond_tokens(
+        treasury_bonds,
+        &ctx.accounts.bond_mint,
+        &ctx.accounts.bond_tokens,
+        &ctx.accounts.bond_token_program,
+    )?;
+
+    Ok(())
+}
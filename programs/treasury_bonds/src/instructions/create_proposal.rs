@@ -0,0 +1,79 @@
+//! CreateProposal instruction handler
+
+use {
+    crate::{
+        error::TreasuryBondsError,
+        state::{configs::TreasuryBondsConfigs, proposal::Proposal},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    #[account(mut,
+        constraint = treasury_bonds_configs.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        constraint = treasury_bonds_configs.is_approver(&owner.key()) @ TreasuryBondsError::NotAnApprover,
+        seeds = [b"treasury-bonds-configs"],
+        bump
+    )]
+    pub treasury_bonds_configs: Box<Account<'info, TreasuryBondsConfigs>>,
+    // init means to create account
+    // bump to use unique address for account
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Proposal::INIT_SPACE,
+        seeds = [
+            b"proposal",
+            treasury_bonds_configs.next_proposal_id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CreateProposalParams {
+    pub action: u8, // admin action i.e register treasury bonds, declare maturity, withdraw vault, set approvers, update tax profile
+    pub target: Pubkey, // account the action applies to i.e the treasury bonds or configs
+    pub payload_hash: [u8; 32], // sha256 hash of the serialized instruction params of the action
+}
+
+// records a pending admin action, the proposer's approval is counted
+pub fn create_proposal(ctx: Context<CreateProposal>, params: &CreateProposalParams) -> Result<()> {
+    msg!("Validate inputs");
+    if !Proposal::is_valid_action(params.action) {
+        return Err(TreasuryBondsError::InvalidProposalAction.into());
+    }
+
+    let treasury_bonds_configs = &mut ctx.accounts.treasury_bonds_configs;
+    let proposal = &mut ctx.accounts.proposal;
+    let owner = &ctx.accounts.owner;
+
+    let now = Clock::get()?.unix_timestamp;
+    let expires_at = now
+        .checked_add(treasury_bonds_configs.proposal_timeout)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    // proposal
+    proposal.proposal_id = treasury_bonds_configs.next_proposal_id;
+    proposal.proposer = owner.key();
+    proposal.action = params.action;
+    proposal.target = params.target;
+    proposal.payload_hash = params.payload_hash;
+    proposal.approvals = vec![owner.key()];
+    proposal.created_at = now;
+    proposal.expires_at = expires_at;
+    proposal.bump = ctx.bumps.proposal;
+
+    treasury_bonds_configs.next_proposal_id = treasury_bonds_configs
+        .next_proposal_id
+        .checked_add(1)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    Ok(())
+}
@@ -1,22 +1,18 @@
 //! CreateToken instruction handler
 
 use {
-    crate::{error::TreasuryBondsError, state::treasury_bonds::TreasuryBonds},
+    crate::error::TreasuryBondsError,
     anchor_lang::{prelude::*, system_program},
     anchor_spl::{
         associated_token,
         associated_token::AssociatedToken,
-        token::{initialize_mint, mint_to, InitializeMint, Mint, MintTo, Token, TokenAccount},
+        token::{initialize_mint, mint_to, InitializeMint, MintTo, Token},
     },
 };
 
 #[derive(Accounts)]
 #[instruction(params: CreateTokenParams)]
 pub struct CreateToken<'info> {
-    #[account(mut,
-        constraint = treasury_bonds.is_initialized @ TreasuryBondsError::AccountNotInitialized
-    )]
-    pub treasury_bonds: Account<'info, TreasuryBonds>,
     #[account(mut)]
     ///CHECK:
     pub token_account: AccountInfo<'info>,
@@ -34,6 +30,7 @@ pub struct CreateToken<'info> {
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct CreateTokenParams {
     pub amount: u32,
+    pub decimals: u8, // decimals for the token mint
 }
 
 pub fn create_token(ctx: Context<CreateToken>, params: &CreateTokenParams) -> Result<()> {
@@ -42,12 +39,15 @@ pub fn create_token(ctx: Context<CreateToken>, params: &CreateTokenParams) -> Re
         return Err(TreasuryBondsError::InvalidAmount.into());
     }
 
-    let treasury_bonds = &ctx.accounts.treasury_bonds;
-    let decimals = treasury_bonds.decimals;
+    if params.decimals == 0 {
+        return Err(TreasuryBondsError::InvalidNumeric.into());
+    }
+
+    let decimals = params.decimals;
     let _amount = params.amount;
 
     let base: u32 = 10;
-    let exponent = treasury_bonds.decimals as u32;
+    let exponent = decimals as u32;
 
     // lets get the amount in decimal format
     // 10 ** 9 * 3(base 10, 9 decimals, 3 amount), // 3 amount of token to transfer (in smallest unit i.e 9 decimals)
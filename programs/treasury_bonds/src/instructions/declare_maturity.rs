@@ -0,0 +1,72 @@
+//! DeclareMaturity instruction handler
+
+use {
+    crate::{
+        error::TreasuryBondsError,
+        state::{
+            configs::{TreasuryBondsConfigs, ISSUANCE_OPERATOR},
+            proposal::{Proposal, DECLARE_MATURITY},
+            treasury_bonds::TreasuryBonds,
+        },
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct DeclareMaturity<'info> {
+    #[account(
+        constraint = treasury_bonds_configs.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        constraint = treasury_bonds_configs.has_role(ISSUANCE_OPERATOR, &owner.key()) @ TreasuryBondsError::MissingRole,
+        seeds = [b"treasury-bonds-configs"],
+        bump
+    )]
+    pub treasury_bonds_configs: Box<Account<'info, TreasuryBondsConfigs>>,
+    // declaring maturity should be approved by the approvers
+    #[account(mut,
+        constraint = !proposal.is_executed @ TreasuryBondsError::ProposalAlreadyExecuted,
+        constraint = proposal.is_approved(&treasury_bonds_configs) @ TreasuryBondsError::ProposalNotApproved,
+        seeds = [b"proposal", proposal.proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+    #[account(mut,
+        constraint = treasury_bonds.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [
+            b"treasury-bonds",
+            treasury_bonds.owner.as_ref(),
+            treasury_bonds.issue_no.as_bytes()
+        ],
+        bump = treasury_bonds.bump
+    )]
+    pub treasury_bonds: Account<'info, TreasuryBonds>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+// admin override for early call events i.e treasury bonds redeemed before the redemption date
+pub fn declare_maturity(ctx: Context<DeclareMaturity>) -> Result<()> {
+    let treasury_bonds = &mut ctx.accounts.treasury_bonds;
+    let proposal = &mut ctx.accounts.proposal;
+
+    // action should be authorized by an approved proposal
+    let now = Clock::get()?.unix_timestamp;
+    if proposal.has_expired(now) {
+        return Err(TreasuryBondsError::ProposalExpired.into());
+    }
+
+    if !proposal.authorizes(DECLARE_MATURITY, &treasury_bonds.key(), &[]) {
+        return Err(TreasuryBondsError::ProposalMismatch.into());
+    }
+
+    proposal.is_executed = true;
+
+    // treasury bonds should not have matured
+    if treasury_bonds.has_matured(now) {
+        return Err(TreasuryBondsError::InvalidBondMaturityStatus.into());
+    }
+
+    treasury_bonds.is_matured = true;
+
+    Ok(())
+}
@@ -0,0 +1,105 @@
+//! EvictOrder instruction handler
+
+use {
+    crate::{
+        error::TreasuryBondsError,
+        events::OrderEvicted,
+        state::{
+            amortization_schedule::AmortizationSchedule,
+            configs::{TreasuryBondsConfigs, ISSUANCE_OPERATOR},
+            coupon_schedule::CouponSchedule,
+            holding::Holding,
+            investor::Investor,
+            order::Order,
+            order_book::OrderBook,
+            treasury_bonds::TreasuryBonds,
+        },
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct EvictOrder<'info> {
+    #[account(
+        constraint = treasury_bonds_configs.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        constraint = treasury_bonds_configs.has_role(ISSUANCE_OPERATOR, &owner.key()) @ TreasuryBondsError::MissingRole,
+        seeds = [b"treasury-bonds-configs"],
+        bump
+    )]
+    pub treasury_bonds_configs: Box<Account<'info, TreasuryBondsConfigs>>,
+    #[account(
+        constraint = treasury_bonds.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [
+            b"treasury-bonds",
+            treasury_bonds.owner.as_ref(),
+            treasury_bonds.issue_no.as_bytes()
+        ],
+        bump = treasury_bonds.bump
+    )]
+    pub treasury_bonds: Box<Account<'info, TreasuryBonds>>,
+    #[account(has_one = treasury_bonds,
+        constraint = coupon_schedule.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [b"coupon-schedule", treasury_bonds.key().as_ref()],
+        bump
+    )]
+    pub coupon_schedule: Box<Account<'info, CouponSchedule>>,
+    #[account(has_one = treasury_bonds,
+        constraint = amortization_schedule.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [b"amortization-schedule", treasury_bonds.key().as_ref()],
+        bump
+    )]
+    pub amortization_schedule: Box<Account<'info, AmortizationSchedule>>,
+    #[account(mut, has_one = treasury_bonds,
+        seeds = [b"order-book", treasury_bonds.key().as_ref()],
+        bump = order_book.bump
+    )]
+    pub order_book: Box<Account<'info, OrderBook>>,
+    #[account(has_one = order_book,
+        seeds = [b"order", order_book.key().as_ref(), order.order_id.to_le_bytes().as_ref()],
+        bump = order.bump
+    )]
+    pub order: Box<Account<'info, Order>>,
+    #[account(address = order.investor)]
+    pub investor: Box<Account<'info, Investor>>,
+    #[account(has_one = treasury_bonds,
+        seeds = [b"holding", investor.key().as_ref(), treasury_bonds.key().as_ref()],
+        bump = holding.bump
+    )]
+    pub holding: Box<Account<'info, Holding>>,
+    pub owner: Signer<'info>,
+}
+
+// takes an order that can no longer be matched off the order book, so that it does not hold
+// up the orders behind it i.e its investor is deactivated, or coupons or principal that have
+// fallen due are yet to be paid on its holding. the order keeps its escrow until it is cancelled.
+pub fn evict_order(ctx: Context<EvictOrder>) -> Result<()> {
+    let treasury_bonds = &ctx.accounts.treasury_bonds;
+    let coupon_schedule = &ctx.accounts.coupon_schedule;
+    let amortization_schedule = &ctx.accounts.amortization_schedule;
+    let order_book = &mut ctx.accounts.order_book;
+    let order = &ctx.accounts.order;
+    let investor = &ctx.accounts.investor;
+    let holding = &ctx.accounts.holding;
+
+    let now = Clock::get()?.unix_timestamp;
+    let periods_due = coupon_schedule.periods_due(now);
+    let tranches_due = amortization_schedule.tranches_due(now);
+    let is_settled = holding.verify_settled(periods_due, tranches_due).is_ok();
+
+    // orders that can still be matched stay on the book
+    if investor.active && is_settled {
+        return Err(TreasuryBondsError::OrderStillMatchable.into());
+    }
+
+    order_book
+        .remove_order(order.side, order.order_id)
+        .ok_or(TreasuryBondsError::InvalidAccountData)?;
+
+    emit!(OrderEvicted {
+        treasury_bonds: treasury_bonds.key(),
+        order_id: order.order_id,
+        side: order.side,
+    });
+
+    Ok(())
+}
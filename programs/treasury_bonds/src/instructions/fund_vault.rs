@@ -0,0 +1,89 @@
+//! FundVault instruction handler
+
+use {
+    crate::{
+        error::TreasuryBondsError,
+        state::{
+            configs::{TreasuryBondsConfigs, TREASURER},
+            treasury_bonds::TreasuryBonds,
+        },
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
+
+#[derive(Accounts)]
+#[instruction(params: FundVaultParams)]
+pub struct FundVault<'info> {
+    #[account(
+        constraint = treasury_bonds_configs.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        constraint = treasury_bonds_configs.has_role(TREASURER, &owner.key()) @ TreasuryBondsError::MissingRole,
+        seeds = [b"treasury-bonds-configs"],
+        bump
+    )]
+    pub treasury_bonds_configs: Box<Account<'info, TreasuryBondsConfigs>>,
+    #[account(
+        constraint = treasury_bonds.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [
+            b"treasury-bonds",
+            treasury_bonds.owner.as_ref(),
+            treasury_bonds.issue_no.as_bytes()
+        ],
+        bump = treasury_bonds.bump
+    )]
+    pub treasury_bonds: Box<Account<'info, TreasuryBonds>>,
+    #[account(mut,
+        constraint = sender_tokens.mint == mint_token.key() @ TreasuryBondsError::InvalidSettlementMint,
+        constraint = sender_tokens.owner == owner.key() @ TreasuryBondsError::InvalidTokenAccountOwner
+    )]
+    pub sender_tokens: Account<'info, TokenAccount>,
+    // treasury vault paying out coupons, principal and redemptions
+    #[account(mut,
+        constraint = vault_tokens.key() == treasury_bonds.vault_tokens @ TreasuryBondsError::InvalidVaultAccount
+    )]
+    pub vault_tokens: Account<'info, TokenAccount>,
+    #[account(address = treasury_bonds.settlement_mint @ TreasuryBondsError::InvalidSettlementMint)]
+    pub mint_token: Account<'info, Mint>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct FundVaultParams {
+    pub amount: u32, // amount to deposit into the treasury vault
+}
+
+// treasurer deposits funds into the treasury vault ahead of coupon, principal and redemption payouts
+pub fn fund_vault(ctx: Context<FundVault>, params: &FundVaultParams) -> Result<()> {
+    msg!("Validate inputs");
+    if params.amount == 0 {
+        return Err(TreasuryBondsError::InvalidAmount.into());
+    }
+
+    let treasury_bonds = &ctx.accounts.treasury_bonds;
+    let sender_tokens = &ctx.accounts.sender_tokens;
+    let vault_tokens = &ctx.accounts.vault_tokens;
+    let mint_token = &ctx.accounts.mint_token;
+    let token_program = &ctx.accounts.token_program;
+    let decimals: u8 = treasury_bonds.decimals;
+
+    let _amount = treasury_bonds
+        .face_value_of(params.amount)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    // Transfer funds from treasurer to treasury vault
+    let cpi_accounts = TransferChecked {
+        from: sender_tokens.to_account_info(),
+        mint: mint_token.to_account_info(),
+        to: vault_tokens.to_account_info(),
+        authority: ctx.accounts.owner.to_account_info(),
+    };
+
+    let cpi = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+
+    transfer_checked(cpi, _amount, decimals)?;
+
+    Ok(())
+}
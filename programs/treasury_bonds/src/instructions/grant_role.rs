@@ -0,0 +1,53 @@
+//! GrantRole instruction handler
+
+use {
+    crate::{
+        error::TreasuryBondsError,
+        state::configs::{RoleGrant, TreasuryBondsConfigs, MAX_ROLE_GRANTS},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct GrantRole<'info> {
+    #[account(mut,
+        constraint = treasury_bonds_configs.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        constraint = treasury_bonds_configs.admin == owner.key() @ TreasuryBondsError::InvalidAdmin,
+        seeds = [b"treasury-bonds-configs"],
+        bump
+    )]
+    pub treasury_bonds_configs: Account<'info, TreasuryBondsConfigs>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct GrantRoleParams {
+    pub role: u8, // role to grant i.e issuance operator, treasurer, compliance officer
+    pub account: Pubkey, // publickey to grant the role to
+}
+
+pub fn grant_role(ctx: Context<GrantRole>, params: &GrantRoleParams) -> Result<()> {
+    let treasury_bonds_configs = &mut ctx.accounts.treasury_bonds_configs;
+
+    msg!("Validate inputs");
+    if !TreasuryBondsConfigs::is_valid_role(params.role) {
+        return Err(TreasuryBondsError::InvalidRole.into());
+    }
+
+    if treasury_bonds_configs.has_role(params.role, &params.account) {
+        return Err(TreasuryBondsError::RoleAlreadyGranted.into());
+    }
+
+    if treasury_bonds_configs.roles.len() >= MAX_ROLE_GRANTS {
+        return Err(TreasuryBondsError::RoleGrantsExceeded.into());
+    }
+
+    treasury_bonds_configs.roles.push(RoleGrant {
+        role: params.role,
+        account: params.account,
+    });
+
+    Ok(())
+}
@@ -1,7 +1,9 @@
 //! Init instruction handler
 
 use {
-    crate::{error::TreasuryBondsError, state::configs::TreasuryBondsConfigs},
+    crate::{
+        error::TreasuryBondsError, program::TreasuryBonds, state::configs::TreasuryBondsConfigs,
+    },
     anchor_lang::prelude::*,
 };
 
@@ -18,17 +20,32 @@ pub struct Init<'info> {
         bump
     )]
     pub treasury_bonds_configs: Account<'info, TreasuryBondsConfigs>,
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ TreasuryBondsError::InvalidProgramData
+    )]
+    pub program: Program<'info, TreasuryBonds>,
+    // only the upgrade authority of the program can initialize the configs
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(owner.key()) @ TreasuryBondsError::InvalidUpgradeAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
     // mut makes it changeble (mutable)
     #[account(mut)]
     pub owner: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn init(ctx: Context<Init>) -> Result<()> {
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitParams {
+    pub admin: Pubkey, // publickey of the configs admin (super-admin)
+}
+
+pub fn init(ctx: Context<Init>, params: &InitParams) -> Result<()> {
     let treasury_bonds_configs = &mut ctx.accounts.treasury_bonds_configs;
 
     // treasury bonds
     treasury_bonds_configs.is_initialized = true;
+    treasury_bonds_configs.admin = params.admin;
 
     Ok(())
 }
@@ -0,0 +1,443 @@
+//! MatchOrders instruction handler
+
+use {
+    crate::{
+        bond_units::{freeze_bond_tokens, thaw_bond_tokens},
+        error::TreasuryBondsError,
+        events::{OrderEvicted, OrdersMatched},
+        state::{
+            amortization_schedule::AmortizationSchedule,
+            coupon_schedule::CouponSchedule,
+            holding::Holding,
+            investor::Investor,
+            order::Order,
+            order_book::{OrderBook, ASK_ORDER, BID_ORDER},
+            treasury_bonds::{TreasuryBonds, ZERO_COUPON_BOND},
+        },
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        token::{transfer, Mint, Token, TokenAccount, Transfer},
+        token_2022::spl_token_2022::onchain::invoke_transfer_checked,
+        token_interface::{self, TokenInterface},
+    },
+};
+
+#[derive(Accounts)]
+pub struct MatchOrders<'info> {
+    #[account(mut,
+        constraint = treasury_bonds.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [
+            b"treasury-bonds",
+            treasury_bonds.owner.as_ref(),
+            treasury_bonds.issue_no.as_bytes()
+        ],
+        bump = treasury_bonds.bump
+    )]
+    pub treasury_bonds: Box<Account<'info, TreasuryBonds>>,
+    #[account(has_one = treasury_bonds,
+        constraint = coupon_schedule.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [b"coupon-schedule", treasury_bonds.key().as_ref()],
+        bump
+    )]
+    pub coupon_schedule: Box<Account<'info, CouponSchedule>>,
+    #[account(has_one = treasury_bonds,
+        constraint = amortization_schedule.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [b"amortization-schedule", treasury_bonds.key().as_ref()],
+        bump
+    )]
+    pub amortization_schedule: Box<Account<'info, AmortizationSchedule>>,
+    #[account(mut, has_one = treasury_bonds,
+        seeds = [b"order-book", treasury_bonds.key().as_ref()],
+        bump = order_book.bump
+    )]
+    pub order_book: Box<Account<'info, OrderBook>>,
+    #[account(mut, has_one = order_book,
+        constraint = bid_order.side == BID_ORDER @ TreasuryBondsError::InvalidOrderSide,
+        seeds = [b"order", order_book.key().as_ref(), bid_order.order_id.to_le_bytes().as_ref()],
+        bump = bid_order.bump
+    )]
+    pub bid_order: Box<Account<'info, Order>>,
+    #[account(mut, has_one = order_book,
+        constraint = ask_order.side == ASK_ORDER @ TreasuryBondsError::InvalidOrderSide,
+        seeds = [b"order", order_book.key().as_ref(), ask_order.order_id.to_le_bytes().as_ref()],
+        bump = ask_order.bump
+    )]
+    pub ask_order: Box<Account<'info, Order>>,
+    #[account(address = bid_order.investor,
+        constraint = buyer_investor.active @ TreasuryBondsError::InvalidInvestorStatus,
+        constraint = buyer_investor.key() != seller_investor.key() @ TreasuryBondsError::SelfTrade
+    )]
+    pub buyer_investor: Box<Account<'info, Investor>>,
+    #[account(address = ask_order.investor,
+        constraint = seller_investor.active @ TreasuryBondsError::InvalidInvestorStatus
+    )]
+    pub seller_investor: Box<Account<'info, Investor>>,
+    #[account(mut, has_one = treasury_bonds,
+        seeds = [b"holding", buyer_investor.key().as_ref(), treasury_bonds.key().as_ref()],
+        bump = buyer_holding.bump
+    )]
+    pub buyer_holding: Box<Account<'info, Holding>>,
+    #[account(mut, has_one = treasury_bonds,
+        seeds = [b"holding", seller_investor.key().as_ref(), treasury_bonds.key().as_ref()],
+        bump = seller_holding.bump
+    )]
+    pub seller_holding: Box<Account<'info, Holding>>,
+    #[account(mut,
+        associated_token::mint = mint_token,
+        associated_token::authority = order_book
+    )]
+    pub escrow_tokens: Box<Account<'info, TokenAccount>>,
+    // buyer is refunded any escrowed amount above the trade price
+    #[account(mut,
+        constraint = buyer_tokens.mint == mint_token.key() @ TreasuryBondsError::InvalidSettlementMint,
+        constraint = buyer_tokens.owner == buyer.key() @ TreasuryBondsError::InvalidTokenAccountOwner
+    )]
+    pub buyer_tokens: Box<Account<'info, TokenAccount>>,
+    // seller is paid the trade price
+    #[account(mut,
+        constraint = seller_tokens.mint == mint_token.key() @ TreasuryBondsError::InvalidSettlementMint,
+        constraint = seller_tokens.owner == seller.key() @ TreasuryBondsError::InvalidTokenAccountOwner
+    )]
+    pub seller_tokens: Box<Account<'info, TokenAccount>>,
+    #[account(address = treasury_bonds.settlement_mint @ TreasuryBondsError::InvalidSettlementMint)]
+    pub mint_token: Box<Account<'info, Mint>>,
+    #[account(
+        address = treasury_bonds.bond_mint @ TreasuryBondsError::InvalidBondMint
+    )]
+    pub bond_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    #[account(mut,
+        associated_token::mint = bond_mint,
+        associated_token::authority = order_book,
+        associated_token::token_program = bond_token_program
+    )]
+    pub escrow_bond_tokens: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    // bond units are delivered out of escrow to the buyer
+    #[account(mut,
+        associated_token::mint = bond_mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = bond_token_program
+    )]
+    pub buyer_bond_tokens: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    // buyer and seller receive the rent of their orders once filled
+    #[account(mut, address = bid_order.owner)]
+    pub buyer: SystemAccount<'info>,
+    #[account(mut, address = ask_order.owner)]
+    pub seller: SystemAccount<'info>,
+    pub token_program: Program<'info, Token>,
+    pub bond_token_program: Interface<'info, TokenInterface>,
+}
+
+// matches the best bid against the best ask of the order book i.e price-time priority.
+// each call fills a single pair of orders at the price of the earlier order plus the coupon
+// accrued since the last coupon date, so that the work done stays within the compute budget;
+// it is called again until the book no longer crosses.
+// anyone can match orders since the trade terms are set by the orders themselves.
+// best orders that can no longer be matched are taken off the book with evict_order.
+pub fn match_orders<'info>(ctx: Context<'_, '_, '_, 'info, MatchOrders<'info>>) -> Result<()> {
+    let treasury_bonds = &mut ctx.accounts.treasury_bonds;
+    let coupon_schedule = &ctx.accounts.coupon_schedule;
+    let amortization_schedule = &ctx.accounts.amortization_schedule;
+    let order_book = &mut ctx.accounts.order_book;
+    let bid_order = &mut ctx.accounts.bid_order;
+    let ask_order = &mut ctx.accounts.ask_order;
+    let buyer_holding = &mut ctx.accounts.buyer_holding;
+    let seller_holding = &mut ctx.accounts.seller_holding;
+    let unit_cost_of_treasury_bonds: u32 = treasury_bonds.unit_cost_of_treasury_bonds;
+    let total_units_treasury_bonds_seller: u32 = seller_holding.total_units_treasury_bonds;
+    let available_funds_seller: u32 = seller_holding.available_funds;
+    let total_units_treasury_bonds_buyer: u32 = buyer_holding.total_units_treasury_bonds;
+    let available_funds_buyer: u32 = buyer_holding.available_funds;
+
+    // treasury bonds should not have matured
+    let now = Clock::get()?.unix_timestamp;
+    if treasury_bonds.has_matured(now) {
+        return Err(TreasuryBondsError::InvalidBondMaturityStatus.into());
+    }
+
+    // only the best bid and the best ask can be matched
+    match (
+        order_book.best_order(BID_ORDER),
+        order_book.best_order(ASK_ORDER),
+    ) {
+        (Some(best_bid), Some(best_ask))
+            if best_bid.order_id == bid_order.order_id
+                && best_ask.order_id == ask_order.order_id => {}
+        _ => return Err(TreasuryBondsError::OrderNotAtTopOfBook.into()),
+    }
+
+    // bid price should meet the ask price
+    if bid_order.clean_price < ask_order.clean_price {
+        return Err(TreasuryBondsError::OrdersNotCrossed.into());
+    }
+
+    // trade is done at the price of the order resting on the book first
+    let clean_price = if bid_order.order_id < ask_order.order_id {
+        bid_order.clean_price
+    } else {
+        ask_order.clean_price
+    };
+
+    let _amount = bid_order.amount.min(ask_order.amount);
+
+    // investor's(seller) available funds should cover the amount traded
+    if available_funds_seller < _amount {
+        return Err(TreasuryBondsError::InsufficientFunds.into());
+    }
+
+    // seller and buyer should have been paid the coupons and principal that have fallen due
+    let periods_due = coupon_schedule.periods_due(now);
+    let tranches_due = amortization_schedule.tranches_due(now);
+    seller_holding.verify_settled(periods_due, tranches_due)?;
+    buyer_holding.verify_settled(periods_due, tranches_due)?;
+
+    // Get unit_treasury_bonds from the product of unit_cost_of_treasury_bonds and _amount
+    let unit_treasury_bonds = unit_cost_of_treasury_bonds
+        .checked_mul(_amount)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    let face_value = treasury_bonds
+        .face_value_of(_amount)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    // amortizing bonds trade on the face value still outstanding
+    let face_value =
+        AmortizationSchedule::share_of(face_value, amortization_schedule.outstanding_rate_at(now))
+            .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    // buyer pays the clean trade price
+    let clean_amount = AmortizationSchedule::share_of(
+        treasury_bonds
+            .clean_value_of(_amount, clean_price)
+            .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?,
+        amortization_schedule.outstanding_rate_at(now),
+    )
+    .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    // and compensates the seller for the coupon accrued since the last coupon date,
+    // which the buyer receives in full at the next coupon date
+    let accrued_coupon = if treasury_bonds.type_of_bond == ZERO_COUPON_BOND {
+        0
+    } else {
+        coupon_schedule
+            .accrued_coupon(
+                face_value,
+                treasury_bonds.coupon_rate,
+                treasury_bonds.day_count,
+                now,
+            )
+            .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?
+    };
+
+    // buyer pays the dirty trade price out of the bid's escrow
+    let trade_amount = clean_amount
+        .checked_add(accrued_coupon)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    // escrow released for the amount traded i.e its share of the bid's escrow,
+    // the whole escrow once the bid is filled
+    let released_amount = if _amount == bid_order.amount {
+        bid_order.escrowed_amount
+    } else {
+        let released_amount = (bid_order.escrowed_amount as u128)
+            .checked_mul(_amount as u128)
+            .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?
+            .checked_div(bid_order.amount as u128)
+            .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+        u64::try_from(released_amount)
+            .map_err(|_| TreasuryBondsError::InvalidArithmeticOperation)?
+    };
+
+    // escrow should cover the dirty trade price
+    let refund_amount = released_amount
+        .checked_sub(trade_amount)
+        .ok_or(TreasuryBondsError::InsufficientFunds)?;
+
+    bid_order.escrowed_amount = bid_order
+        .escrowed_amount
+        .checked_sub(released_amount)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+    bid_order.amount -= _amount;
+    ask_order.amount -= _amount;
+
+    // Deduct sold treasury bonds' cost basis from seller_holding's cost_basis
+    let cost_basis_seller = seller_holding
+        .cost_basis_of(_amount)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+    seller_holding.cost_basis = seller_holding
+        .cost_basis
+        .checked_sub(cost_basis_seller)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    // Deduct sold unit_treasury_bonds from seller_holding's total_units_treasury_bonds
+    seller_holding.total_units_treasury_bonds = total_units_treasury_bonds_seller
+        .checked_sub(unit_treasury_bonds)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    // Deduct _amount from seller_holding's available funds
+    seller_holding.available_funds = available_funds_seller
+        .checked_sub(_amount)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    // A seller with no available funds left is removed from the holders count
+    if seller_holding.available_funds == 0 {
+        treasury_bonds.holders_count = treasury_bonds
+            .holders_count
+            .checked_sub(1)
+            .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+    }
+
+    // buyer only earns coupons and principal falling due after the purchase
+    buyer_holding.coupon_periods_paid = periods_due;
+    buyer_holding.principal_tranches_repaid = tranches_due;
+
+    // A new bondholder is added to the holders count
+    if available_funds_buyer == 0 {
+        treasury_bonds.holders_count = treasury_bonds
+            .holders_count
+            .checked_add(1)
+            .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+    }
+
+    // Increment buyer's total_units_treasury_bonds with unit_treasury_bonds
+    buyer_holding.total_units_treasury_bonds = total_units_treasury_bonds_buyer
+        .checked_add(unit_treasury_bonds)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    // Increment buyer's available_funds with _amount
+    buyer_holding.available_funds = available_funds_buyer
+        .checked_add(_amount)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    // Increment buyer's cost_basis with the clean price paid
+    buyer_holding.cost_basis = buyer_holding
+        .cost_basis
+        .checked_add(clean_amount)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    let treasury_bonds_key = treasury_bonds.key();
+
+    // filled orders are taken off the book, and so are remainders below the minimum trade
+    // amount, which keep their escrow until the orders are cancelled
+    if bid_order.amount == 0 || bid_order.amount < treasury_bonds.minimum_trade_amount {
+        order_book
+            .remove_order(BID_ORDER, bid_order.order_id)
+            .ok_or(TreasuryBondsError::InvalidAccountData)?;
+
+        if bid_order.amount > 0 {
+            emit!(OrderEvicted {
+                treasury_bonds: treasury_bonds_key,
+                order_id: bid_order.order_id,
+                side: BID_ORDER,
+            });
+        }
+    }
+
+    // asks of the seller's whole remaining position can stay below the minimum trade amount
+    if ask_order.amount == 0
+        || !treasury_bonds.is_valid_trade_amount(ask_order.amount, seller_holding.available_funds)
+    {
+        order_book
+            .remove_order(ASK_ORDER, ask_order.order_id)
+            .ok_or(TreasuryBondsError::InvalidAccountData)?;
+
+        if ask_order.amount > 0 {
+            emit!(OrderEvicted {
+                treasury_bonds: treasury_bonds_key,
+                order_id: ask_order.order_id,
+                side: ASK_ORDER,
+            });
+        }
+    }
+
+    let seeds = &[
+        b"order-book",
+        treasury_bonds_key.as_ref(),
+        &[order_book.bump],
+    ];
+
+    let signer = &[&seeds[..]];
+
+    // Transfer trade amount from escrow to seller
+    if trade_amount > 0 {
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.escrow_tokens.to_account_info(),
+                    to: ctx.accounts.seller_tokens.to_account_info(),
+                    authority: order_book.to_account_info(),
+                },
+                signer,
+            ),
+            trade_amount,
+        )?;
+    }
+
+    // Refund the escrowed amount above the trade price to the buyer
+    if refund_amount > 0 {
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.escrow_tokens.to_account_info(),
+                    to: ctx.accounts.buyer_tokens.to_account_info(),
+                    authority: order_book.to_account_info(),
+                },
+                signer,
+            ),
+            refund_amount,
+        )?;
+    }
+
+    // Thaw the buyer's bond units for the program to deliver them
+    thaw_bond_tokens(
+        treasury_bonds,
+        &ctx.accounts.bond_mint,
+        &ctx.accounts.buyer_bond_tokens,
+        &ctx.accounts.bond_token_program,
+    )?;
+
+    // Transfer bond units from escrow to buyer, against the payment above
+    // remaining accounts carry the transfer hook accounts of bond mints in Token-2022 mode
+    invoke_transfer_checked(
+        ctx.accounts.bond_token_program.key,
+        ctx.accounts.escrow_bond_tokens.to_account_info(),
+        ctx.accounts.bond_mint.to_account_info(),
+        ctx.accounts.buyer_bond_tokens.to_account_info(),
+        order_book.to_account_info(),
+        ctx.remaining_accounts,
+        unit_treasury_bonds as u64,
+        ctx.accounts.bond_mint.decimals,
+        signer,
+    )?;
+
+    // Freeze the buyer's bond units again
+    freeze_bond_tokens(
+        treasury_bonds,
+        &ctx.accounts.bond_mint,
+        &ctx.accounts.buyer_bond_tokens,
+        &ctx.accounts.bond_token_program,
+    )?;
+
+    emit!(OrdersMatched {
+        treasury_bonds: treasury_bonds_key,
+        bid_order_id: bid_order.order_id,
+        ask_order_id: ask_order.order_id,
+        clean_price,
+        amount: _amount,
+        accrued_coupon,
+        settlement_amount: trade_amount,
+    });
+
+    // orders are closed once filled, returning their rent to the investors
+    if bid_order.amount == 0 {
+        bid_order.close(ctx.accounts.buyer.to_account_info())?;
+    }
+
+    if ask_order.amount == 0 {
+        ask_order.close(ctx.accounts.seller.to_account_info())?;
+    }
+
+    Ok(())
+}
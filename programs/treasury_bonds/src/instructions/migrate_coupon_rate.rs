@@ -0,0 +1,132 @@
+//! MigrateCouponRate instruction handler
+
+use {
+    crate::{
+        error::TreasuryBondsError,
+        pricing::{percent_to_coupon_rate, BASIS_POINTS},
+        state::treasury_bonds::{PercentCouponTreasuryBonds, TreasuryBonds},
+    },
+    anchor_lang::{prelude::*, system_program, Discriminator},
+};
+
+#[derive(Accounts)]
+pub struct MigrateCouponRate<'info> {
+    #[account(mut, owner = crate::ID)]
+    /// CHECK: account data in the whole percentage layout is deserialized and validated in the instruction handler.
+    pub treasury_bonds: UncheckedAccount<'info>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// one-off migration of treasury bonds registered while coupon rates were whole percentages
+// to the layout storing coupon rates in ten-thousandths of a percent (discount rates in basis points).
+// the account is reallocated in place, the admin pays for the extra space.
+pub fn migrate_coupon_rate(ctx: Context<MigrateCouponRate>) -> Result<()> {
+    let treasury_bonds_info = ctx.accounts.treasury_bonds.to_account_info();
+    let owner = &ctx.accounts.owner;
+
+    let percent_coupon_treasury_bonds = {
+        let data = treasury_bonds_info.try_borrow_data()?;
+
+        // accounts in the whole percentage layout were allocated with its space
+        if data.len() != 8 + PercentCouponTreasuryBonds::INIT_SPACE {
+            return Err(TreasuryBondsError::AccountAlreadyMigrated.into());
+        }
+
+        if data[..8] != TreasuryBonds::DISCRIMINATOR {
+            return Err(TreasuryBondsError::InvalidAccountData.into());
+        }
+
+        PercentCouponTreasuryBonds::deserialize(&mut &data[8..])
+            .map_err(|_| TreasuryBondsError::InvalidAccountData)?
+    };
+
+    if percent_coupon_treasury_bonds.owner != *owner.key
+        || !percent_coupon_treasury_bonds.is_initialized
+    {
+        return Err(TreasuryBondsError::InvalidAccountData.into());
+    }
+
+    // account should be the treasury bonds PDA i.e seeded by admin and issue no
+    let treasury_bonds_key = Pubkey::create_program_address(
+        &[
+            b"treasury-bonds",
+            percent_coupon_treasury_bonds.owner.as_ref(),
+            percent_coupon_treasury_bonds.issue_no.as_bytes(),
+            &[percent_coupon_treasury_bonds.bump],
+        ],
+        &crate::ID,
+    )
+    .map_err(|_| TreasuryBondsError::InvalidAccountData)?;
+
+    if treasury_bonds_key != treasury_bonds_info.key() {
+        return Err(TreasuryBondsError::InvalidAccountData.into());
+    }
+
+    // discount rates of zero coupon bonds were stored as whole percentages
+    let discount_rate = (percent_coupon_treasury_bonds.discount_rate as u64)
+        .checked_mul(BASIS_POINTS)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?
+        .checked_div(100)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+    let discount_rate =
+        u16::try_from(discount_rate).map_err(|_| TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    let treasury_bonds = TreasuryBonds {
+        owner: percent_coupon_treasury_bonds.owner,
+        issuer: percent_coupon_treasury_bonds.issuer,
+        country: percent_coupon_treasury_bonds.country,
+        issue_no: percent_coupon_treasury_bonds.issue_no,
+        type_of_bond: percent_coupon_treasury_bonds.type_of_bond,
+        tenor: percent_coupon_treasury_bonds.tenor,
+        coupon_rate: percent_to_coupon_rate(percent_coupon_treasury_bonds.coupon_rate),
+        discount_rate,
+        total_amounts_offered: percent_coupon_treasury_bonds.total_amounts_offered,
+        total_amounts_accepted: percent_coupon_treasury_bonds.total_amounts_accepted,
+        total_available_funds: percent_coupon_treasury_bonds.total_available_funds,
+        minimum_bid_amount: percent_coupon_treasury_bonds.minimum_bid_amount,
+        is_initialized: percent_coupon_treasury_bonds.is_initialized,
+        holders_count: percent_coupon_treasury_bonds.holders_count,
+        unit_cost_of_treasury_bonds: percent_coupon_treasury_bonds.unit_cost_of_treasury_bonds,
+        decimals: percent_coupon_treasury_bonds.decimals,
+        value_date: percent_coupon_treasury_bonds.value_date,
+        redemption_date: percent_coupon_treasury_bonds.redemption_date,
+        is_matured: percent_coupon_treasury_bonds.is_matured,
+        deposit_account: percent_coupon_treasury_bonds.deposit_account,
+        settlement_mint: percent_coupon_treasury_bonds.settlement_mint,
+        minimum_trade_amount: percent_coupon_treasury_bonds.minimum_trade_amount,
+        allows_early_redemption: percent_coupon_treasury_bonds.allows_early_redemption,
+        day_count: percent_coupon_treasury_bonds.day_count,
+        bond_mint: percent_coupon_treasury_bonds.bond_mint,
+        vault_tokens: percent_coupon_treasury_bonds.vault_tokens,
+        bump: percent_coupon_treasury_bonds.bump,
+    };
+
+    // top up the rent for the reallocated space
+    let space = 8 + TreasuryBonds::INIT_SPACE;
+    let rent_shortfall = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(treasury_bonds_info.lamports());
+
+    if rent_shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: owner.to_account_info(),
+                    to: treasury_bonds_info.clone(),
+                },
+            ),
+            rent_shortfall,
+        )?;
+    }
+
+    treasury_bonds_info.realloc(space, false)?;
+
+    let mut data = treasury_bonds_info.try_borrow_mut_data()?;
+    treasury_bonds.try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
@@ -0,0 +1,211 @@
+//! MigrateInvestor instruction handler
+
+use {
+    crate::{
+        bond_units::{freeze_bond_tokens, thaw_bond_tokens},
+        error::TreasuryBondsError,
+        state::{
+            amortization_schedule::AmortizationSchedule,
+            holding::Holding,
+            investor::{Investor, LegacyInvestor},
+            treasury_bonds::TreasuryBonds,
+        },
+    },
+    anchor_lang::{prelude::*, Discriminator},
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token_interface::{self, TokenInterface},
+    },
+};
+
+#[derive(Accounts)]
+pub struct MigrateInvestor<'info> {
+    #[account(has_one = owner,
+        constraint = treasury_bonds.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [
+            b"treasury-bonds",
+            treasury_bonds.owner.as_ref(),
+            treasury_bonds.issue_no.as_bytes()
+        ],
+        bump = treasury_bonds.bump
+    )]
+    pub treasury_bonds: Box<Account<'info, TreasuryBonds>>,
+    #[account(has_one = treasury_bonds,
+        constraint = amortization_schedule.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [b"amortization-schedule", treasury_bonds.key().as_ref()],
+        bump
+    )]
+    pub amortization_schedule: Box<Account<'info, AmortizationSchedule>>,
+    #[account(mut, owner = crate::ID,
+        seeds = [b"investor", investor_owner.key().as_ref()],
+        bump
+    )]
+    /// CHECK: legacy account data is deserialized and validated in the instruction handler.
+    pub investor: UncheckedAccount<'info>,
+    // init means to create account
+    // bump to use unique address for account
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Holding::INIT_SPACE,
+        seeds = [b"holding", investor.key().as_ref(), treasury_bonds.key().as_ref()],
+        bump
+    )]
+    pub holding: Box<Account<'info, Holding>>,
+    #[account(mut,
+        address = treasury_bonds.bond_mint @ TreasuryBondsError::InvalidBondMint
+    )]
+    pub bond_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    // bond units of the legacy position are minted to the investor's token account
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = bond_mint,
+        associated_token::authority = investor_owner,
+        associated_token::token_program = bond_token_program
+    )]
+    pub bond_tokens: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    // investor receives the rent released by the smaller investor account
+    #[account(mut)]
+    pub investor_owner: SystemAccount<'info>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub bond_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+// one-off migration of investor accounts registered while investor positions were held
+// on the investor account itself. the position is moved into a holding of the given
+// treasury bonds, its bond units are minted to the investor and the investor account is
+// reallocated in place. legacy investors were never paid coupons, so they are owed every
+// coupon that has fallen due. migrated holders are already counted in the holders count
+// set when the treasury bonds were migrated.
+pub fn migrate_investor(ctx: Context<MigrateInvestor>) -> Result<()> {
+    let investor_info = ctx.accounts.investor.to_account_info();
+    let treasury_bonds = &ctx.accounts.treasury_bonds;
+    let amortization_schedule = &ctx.accounts.amortization_schedule;
+    let holding = &mut ctx.accounts.holding;
+    let investor_owner = &ctx.accounts.investor_owner;
+
+    let legacy_investor = {
+        let data = investor_info.try_borrow_data()?;
+
+        // accounts in the legacy layout were allocated with the legacy space
+        if data.len() != 8 + LegacyInvestor::INIT_SPACE {
+            return Err(TreasuryBondsError::AccountAlreadyMigrated.into());
+        }
+
+        if data[..8] != Investor::DISCRIMINATOR {
+            return Err(TreasuryBondsError::InvalidAccountData.into());
+        }
+
+        LegacyInvestor::deserialize(&mut &data[8..])
+            .map_err(|_| TreasuryBondsError::InvalidAccountData)?
+    };
+
+    if legacy_investor.owner != investor_owner.key() {
+        return Err(TreasuryBondsError::InvalidAccountData.into());
+    }
+
+    // legacy treasury bonds repaid the face value at maturity
+    if !amortization_schedule.tranches.is_empty() {
+        return Err(TreasuryBondsError::InvalidAmortizationSchedule.into());
+    }
+
+    // units held should match the unit cost of the treasury bonds
+    let total_units_treasury_bonds = treasury_bonds
+        .unit_cost_of_treasury_bonds
+        .checked_mul(legacy_investor.available_funds)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    if legacy_investor.total_units_treasury_bonds != total_units_treasury_bonds {
+        return Err(TreasuryBondsError::InvalidAccountData.into());
+    }
+
+    // legacy treasury bonds were bought at par
+    let cost_basis = treasury_bonds
+        .face_value_of(legacy_investor.available_funds)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    // holding
+    holding.investor = investor_info.key();
+    holding.treasury_bonds = treasury_bonds.key();
+    holding.total_units_treasury_bonds = legacy_investor.total_units_treasury_bonds;
+    holding.available_funds = legacy_investor.available_funds;
+    holding.cost_basis = cost_basis;
+    // coupons are owed from the value date
+    holding.coupon_periods_paid = 0;
+    holding.principal_tranches_repaid = 0;
+    holding.is_initialized = true;
+    holding.bump = ctx.bumps.holding;
+
+    let investor = Investor {
+        owner: legacy_investor.owner,
+        full_names: legacy_investor.full_names,
+        country: legacy_investor.country,
+        active: legacy_investor.active,
+    };
+
+    // release the rent of the space no longer used to the investor
+    let space = 8 + Investor::INIT_SPACE;
+    let rent_surplus = investor_info
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(space));
+
+    if rent_surplus > 0 {
+        **investor_info.try_borrow_mut_lamports()? -= rent_surplus;
+        **investor_owner.to_account_info().try_borrow_mut_lamports()? += rent_surplus;
+    }
+
+    investor_info.realloc(space, false)?;
+
+    {
+        let mut data = investor_info.try_borrow_mut_data()?;
+        investor.try_serialize(&mut &mut data[..])?;
+    }
+
+    let seeds = &[
+        b"treasury-bonds",
+        treasury_bonds.owner.as_ref(),
+        treasury_bonds.issue_no.as_bytes(),
+        &[treasury_bonds.bump],
+    ];
+
+    let signer = &[&seeds[..]];
+
+    // Thaw the investor's bond units for the program to mint to them
+    thaw_bond_tokens(
+        treasury_bonds,
+        &ctx.accounts.bond_mint,
+        &ctx.accounts.bond_tokens,
+        &ctx.accounts.bond_token_program,
+    )?;
+
+    // Mint bond units of the legacy position to the investor
+    if total_units_treasury_bonds > 0 {
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.bond_token_program.to_account_info(),
+                token_interface::MintTo {
+                    mint: ctx.accounts.bond_mint.to_account_info(),
+                    to: ctx.accounts.bond_tokens.to_account_info(),
+                    authority: treasury_bonds.to_account_info(),
+                },
+                signer,
+            ),
+            total_units_treasury_bonds as u64,
+        )?;
+    }
+
+    // Freeze the investor's bond units again
+    freeze_bond_tokens(
+        treasury_bonds,
+        &ctx.accounts.bond_mint,
+        &ctx.accounts.bond_tokens,
+        &ctx.accounts.bond_token_program,
+    )?;
+
+    Ok(())
+}
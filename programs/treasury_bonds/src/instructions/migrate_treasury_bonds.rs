@@ -0,0 +1,250 @@
+//! MigrateTreasuryBonds instruction handler
+
+use {
+    crate::{
+        error::TreasuryBondsError,
+        pricing::{
+            days_from_civil, days_in_month, percent_to_coupon_rate, ACTUAL_ACTUAL_ICMA,
+            SECONDS_PER_DAY,
+        },
+        state::{
+            amortization_schedule::AmortizationSchedule,
+            configs::{TreasuryBondsConfigs, ISSUANCE_OPERATOR},
+            coupon_schedule::CouponSchedule,
+            deposit_base::DepositBase,
+            treasury_bonds::{LegacyTreasuryBonds, TreasuryBonds},
+        },
+    },
+    anchor_lang::{prelude::*, system_program, Discriminator},
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token::{Mint, Token, TokenAccount},
+        token_interface::{self, TokenInterface},
+    },
+};
+
+#[derive(Accounts)]
+#[instruction(params: MigrateTreasuryBondsParams)]
+pub struct MigrateTreasuryBonds<'info> {
+    #[account(mut, owner = crate::ID,
+        seeds = [b"treasury-bonds", owner.key().as_ref()],
+        bump
+    )]
+    /// CHECK: legacy account data is deserialized and validated in the instruction handler.
+    pub legacy_treasury_bonds: UncheckedAccount<'info>,
+    // init means to create account
+    // bump to use unique address for account
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + TreasuryBonds::INIT_SPACE,
+        seeds = [b"treasury-bonds", owner.key().as_ref(), params.issue_no.as_bytes()],
+        bump
+    )]
+    pub treasury_bonds: Box<Account<'info, TreasuryBonds>>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + CouponSchedule::INIT_SPACE,
+        seeds = [b"coupon-schedule", treasury_bonds.key().as_ref()],
+        bump
+    )]
+    pub coupon_schedule: Account<'info, CouponSchedule>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + AmortizationSchedule::INIT_SPACE,
+        seeds = [b"amortization-schedule", treasury_bonds.key().as_ref()],
+        bump
+    )]
+    pub amortization_schedule: Box<Account<'info, AmortizationSchedule>>,
+    // deposit account registered alongside the legacy treasury bonds
+    #[account(has_one = owner,
+        constraint = deposit_account.is_initialized @ TreasuryBondsError::AccountNotInitialized
+    )]
+    pub deposit_account: Account<'info, DepositBase>,
+    #[account(seeds = [b"auth", deposit_account.key().as_ref()], bump)]
+    /// CHECK: no need to check this.
+    pub pda_auth: UncheckedAccount<'info>,
+    #[account(seeds = [b"treasury-vault", pda_auth.key().as_ref()], bump)]
+    pub treasury_vault: SystemAccount<'info>,
+    // legacy treasury vaults may already hold a token account for the mint
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint_token,
+        associated_token::authority = treasury_vault
+    )]
+    pub vault_tokens: Box<Account<'info, TokenAccount>>,
+    #[account(
+        constraint = treasury_bonds_configs.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        constraint = treasury_bonds_configs.has_role(ISSUANCE_OPERATOR, &owner.key()) @ TreasuryBondsError::MissingRole,
+        seeds = [b"treasury-bonds-configs"],
+        bump
+    )]
+    pub treasury_bonds_configs: Box<Account<'info, TreasuryBondsConfigs>>,
+    // settlement mint of the treasury bonds i.e USDC or a configured stablecoin
+    #[account(
+        constraint = treasury_bonds_configs.is_settlement_mint(&mint_token.key()) @ TreasuryBondsError::SettlementMintNotAllowed
+    )]
+    pub mint_token: Box<Account<'info, Mint>>,
+    // mint of the bond units, minted to investors on purchase and burnt on redemption
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"bond-mint", treasury_bonds.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = treasury_bonds,
+        mint::freeze_authority = treasury_bonds,
+        mint::token_program = bond_token_program
+    )]
+    pub bond_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub bond_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct MigrateTreasuryBondsParams {
+    issue_no: String, // issue no of the legacy treasury bonds
+}
+
+// one-off migration of treasury bonds registered with free-form date strings
+// to the layout storing value and redemption dates as unix timestamps.
+// the legacy account is moved to the PDA seeded by admin and issue no, then closed.
+pub fn migrate_treasury_bonds(
+    ctx: Context<MigrateTreasuryBonds>,
+    params: &MigrateTreasuryBondsParams,
+) -> Result<()> {
+    let legacy_treasury_bonds_info = ctx.accounts.legacy_treasury_bonds.to_account_info();
+    let treasury_bonds = &mut ctx.accounts.treasury_bonds;
+    let deposit_account = &ctx.accounts.deposit_account;
+    let vault_tokens = &ctx.accounts.vault_tokens;
+    let mint_token = &ctx.accounts.mint_token;
+    let bond_mint = &ctx.accounts.bond_mint;
+    let coupon_schedule = &mut ctx.accounts.coupon_schedule;
+    let amortization_schedule = &mut ctx.accounts.amortization_schedule;
+    let owner = &ctx.accounts.owner;
+
+    let legacy_treasury_bonds = {
+        let data = legacy_treasury_bonds_info.try_borrow_data()?;
+
+        // accounts in the legacy layout were allocated with the legacy space
+        if data.len() != 8 + LegacyTreasuryBonds::INIT_SPACE {
+            return Err(TreasuryBondsError::AccountAlreadyMigrated.into());
+        }
+
+        if data[..8] != TreasuryBonds::DISCRIMINATOR {
+            return Err(TreasuryBondsError::InvalidAccountData.into());
+        }
+
+        LegacyTreasuryBonds::deserialize(&mut &data[8..])
+            .map_err(|_| TreasuryBondsError::InvalidAccountData)?
+    };
+
+    if legacy_treasury_bonds.owner != *owner.key
+        || !legacy_treasury_bonds.is_initialized
+        || legacy_treasury_bonds.issue_no != params.issue_no
+    {
+        return Err(TreasuryBondsError::InvalidAccountData.into());
+    }
+
+    // amounts are converted using the decimals of the settlement mint
+    if legacy_treasury_bonds.decimals != mint_token.decimals {
+        return Err(TreasuryBondsError::InvalidNumeric.into());
+    }
+
+    // legacy treasury bonds did not count their holders, they listed the investors
+    // on each purchase instead
+    let mut investors = legacy_treasury_bonds.investors.clone();
+    investors.sort_unstable();
+    investors.dedup();
+    let holders_count = u32::try_from(investors.len())
+        .map_err(|_| TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    msg!("Validate dates");
+    let value_date = parse_date(&legacy_treasury_bonds.value_date)
+        .ok_or(TreasuryBondsError::InvalidValueDate)?;
+    let redemption_date = parse_date(&legacy_treasury_bonds.redemption_date)
+        .ok_or(TreasuryBondsError::InvalidRedemptionDate)?;
+
+    // redemption date should fall tenor years after the value date
+    if !TreasuryBonds::is_valid_term(value_date, redemption_date, legacy_treasury_bonds.tenor) {
+        return Err(TreasuryBondsError::InvalidRedemptionDate.into());
+    }
+
+    // treasury_bonds
+    treasury_bonds.owner = legacy_treasury_bonds.owner;
+    treasury_bonds.issuer = legacy_treasury_bonds.issuer;
+    treasury_bonds.country = legacy_treasury_bonds.country;
+    treasury_bonds.issue_no = legacy_treasury_bonds.issue_no;
+    treasury_bonds.type_of_bond = legacy_treasury_bonds.type_of_bond;
+    treasury_bonds.tenor = legacy_treasury_bonds.tenor;
+    treasury_bonds.coupon_rate = percent_to_coupon_rate(legacy_treasury_bonds.coupon_rate);
+    treasury_bonds.total_amounts_offered = legacy_treasury_bonds.total_amounts_offered;
+    treasury_bonds.total_amounts_accepted = legacy_treasury_bonds.total_amounts_accepted;
+    treasury_bonds.total_available_funds = legacy_treasury_bonds.total_available_funds;
+    treasury_bonds.holders_count = holders_count;
+    treasury_bonds.minimum_bid_amount = legacy_treasury_bonds.minimum_bid_amount;
+    treasury_bonds.minimum_trade_amount = legacy_treasury_bonds.minimum_bid_amount;
+    // coupons of legacy treasury bonds were paid as half the annual coupon per period
+    treasury_bonds.day_count = ACTUAL_ACTUAL_ICMA;
+    treasury_bonds.is_initialized = true;
+    treasury_bonds.unit_cost_of_treasury_bonds = legacy_treasury_bonds.unit_cost_of_treasury_bonds;
+    treasury_bonds.decimals = legacy_treasury_bonds.decimals;
+    treasury_bonds.value_date = value_date;
+    treasury_bonds.redemption_date = redemption_date;
+    treasury_bonds.is_matured = legacy_treasury_bonds.is_matured;
+    treasury_bonds.deposit_account = deposit_account.key();
+    treasury_bonds.settlement_mint = mint_token.key();
+    treasury_bonds.bond_mint = bond_mint.key();
+    treasury_bonds.vault_tokens = vault_tokens.key();
+    treasury_bonds.bump = ctx.bumps.treasury_bonds;
+
+    // close the legacy account, returning its rent to the admin
+    let legacy_lamports = legacy_treasury_bonds_info.lamports();
+    **owner.to_account_info().try_borrow_mut_lamports()? += legacy_lamports;
+    **legacy_treasury_bonds_info.try_borrow_mut_lamports()? = 0;
+    legacy_treasury_bonds_info.assign(&system_program::ID);
+    legacy_treasury_bonds_info.realloc(0, false)?;
+
+    // coupon_schedule
+    // coupons are paid every six months throughout the bond's maturity, the last on the redemption date
+    coupon_schedule.treasury_bonds = treasury_bonds.key();
+    coupon_schedule.maturity_date = redemption_date;
+    coupon_schedule.total_coupon_periods = treasury_bonds
+        .tenor
+        .checked_mul(2)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+    coupon_schedule.is_initialized = true;
+
+    // amortization_schedule
+    // legacy treasury bonds repay the face value at maturity
+    amortization_schedule.treasury_bonds = treasury_bonds.key();
+    amortization_schedule.is_initialized = true;
+
+    Ok(())
+}
+
+// converts a date in the format DD-MM-YYYY to a unix timestamp (midnight UTC)
+fn parse_date(date: &str) -> Option<i64> {
+    let mut parts = date.trim().split('-');
+    let day: i64 = parts.next()?.parse().ok()?;
+    let month: i64 = parts.next()?.parse().ok()?;
+    let year: i64 = parts.next()?.parse().ok()?;
+
+    if parts.next().is_some() || !(1..=12).contains(&month) || year < 1970 {
+        return None;
+    }
+
+    if day < 1 || day > days_in_month(year, month) {
+        return None;
+    }
+
+    Some(days_from_civil(year, month, day) * SECONDS_PER_DAY)
+}
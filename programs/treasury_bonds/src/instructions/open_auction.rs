@@ -0,0 +1,101 @@
+//! OpenAuction instruction handler
+
+use {
+    crate::{
+        error::TreasuryBondsError,
+        state::{
+            auction::Auction,
+            configs::{TreasuryBondsConfigs, ISSUANCE_OPERATOR},
+            treasury_bonds::TreasuryBonds,
+        },
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token::{Mint, Token, TokenAccount},
+    },
+};
+
+#[derive(Accounts)]
+#[instruction(params: OpenAuctionParams)]
+pub struct OpenAuction<'info> {
+    #[account(
+        constraint = treasury_bonds_configs.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        constraint = treasury_bonds_configs.has_role(ISSUANCE_OPERATOR, &owner.key()) @ TreasuryBondsError::MissingRole,
+        seeds = [b"treasury-bonds-configs"],
+        bump
+    )]
+    pub treasury_bonds_configs: Box<Account<'info, TreasuryBondsConfigs>>,
+    #[account(
+        constraint = treasury_bonds.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [
+            b"treasury-bonds",
+            treasury_bonds.owner.as_ref(),
+            treasury_bonds.issue_no.as_bytes()
+        ],
+        bump = treasury_bonds.bump
+    )]
+    pub treasury_bonds: Account<'info, TreasuryBonds>,
+    // init means to create account
+    // bump to use unique address for account
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Auction::INIT_SPACE,
+        seeds = [b"auction", treasury_bonds.key().as_ref()],
+        bump
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+    // escrow holding the bid amounts until the auction is closed
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = mint_token,
+        associated_token::authority = auction
+    )]
+    pub escrow_tokens: Account<'info, TokenAccount>,
+    #[account(address = treasury_bonds.settlement_mint @ TreasuryBondsError::InvalidSettlementMint)]
+    pub mint_token: Account<'info, Mint>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct OpenAuctionParams {
+    bid_opening_date: i64, // unix timestamp when bids can first be submitted
+    bid_closing_date: i64, // unix timestamp after which no bids can be submitted
+}
+
+pub fn open_auction(ctx: Context<OpenAuction>, params: &OpenAuctionParams) -> Result<()> {
+    // validate inputs
+    msg!("Validate inputs");
+    let now = Clock::get()?.unix_timestamp;
+    if params.bid_opening_date > 0
+        && params.bid_opening_date < params.bid_closing_date
+        && params.bid_closing_date > now
+    {
+    } else {
+        return Err(TreasuryBondsError::InvalidAuctionDates.into());
+    }
+
+    let treasury_bonds = &ctx.accounts.treasury_bonds;
+    let auction = &mut ctx.accounts.auction;
+
+    // treasury bonds should not have matured
+    if treasury_bonds.has_matured(now) {
+        return Err(TreasuryBondsError::InvalidBondMaturityStatus.into());
+    }
+
+    // auction
+    auction.treasury_bonds = treasury_bonds.key();
+    auction.bid_opening_date = params.bid_opening_date;
+    auction.bid_closing_date = params.bid_closing_date;
+    auction.is_initialized = true;
+    auction.bump = ctx.bumps.auction;
+
+    Ok(())
+}
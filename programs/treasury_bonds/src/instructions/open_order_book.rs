@@ -0,0 +1,99 @@
+//! OpenOrderBook instruction handler
+
+use {
+    crate::{
+        error::TreasuryBondsError,
+        state::{
+            configs::{TreasuryBondsConfigs, ISSUANCE_OPERATOR},
+            order_book::OrderBook,
+            treasury_bonds::TreasuryBonds,
+        },
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token::{Mint, Token, TokenAccount},
+        token_interface::{self, TokenInterface},
+    },
+};
+
+#[derive(Accounts)]
+pub struct OpenOrderBook<'info> {
+    #[account(
+        constraint = treasury_bonds_configs.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        constraint = treasury_bonds_configs.has_role(ISSUANCE_OPERATOR, &owner.key()) @ TreasuryBondsError::MissingRole,
+        seeds = [b"treasury-bonds-configs"],
+        bump
+    )]
+    pub treasury_bonds_configs: Box<Account<'info, TreasuryBondsConfigs>>,
+    #[account(
+        constraint = treasury_bonds.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [
+            b"treasury-bonds",
+            treasury_bonds.owner.as_ref(),
+            treasury_bonds.issue_no.as_bytes()
+        ],
+        bump = treasury_bonds.bump
+    )]
+    pub treasury_bonds: Account<'info, TreasuryBonds>,
+    // init means to create account
+    // bump to use unique address for account
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + OrderBook::INIT_SPACE,
+        seeds = [b"order-book", treasury_bonds.key().as_ref()],
+        bump
+    )]
+    pub order_book: Box<Account<'info, OrderBook>>,
+    // escrow holding the settlement amounts of resting bids
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = mint_token,
+        associated_token::authority = order_book
+    )]
+    pub escrow_tokens: Box<Account<'info, TokenAccount>>,
+    // escrow holding the bond units of resting asks
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = bond_mint,
+        associated_token::authority = order_book,
+        associated_token::token_program = bond_token_program
+    )]
+    pub escrow_bond_tokens: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    #[account(address = treasury_bonds.settlement_mint @ TreasuryBondsError::InvalidSettlementMint)]
+    pub mint_token: Box<Account<'info, Mint>>,
+    #[account(
+        address = treasury_bonds.bond_mint @ TreasuryBondsError::InvalidBondMint
+    )]
+    pub bond_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub bond_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+// opens the secondary market order book of the treasury bonds
+pub fn open_order_book(ctx: Context<OpenOrderBook>) -> Result<()> {
+    let treasury_bonds = &ctx.accounts.treasury_bonds;
+    let order_book = &mut ctx.accounts.order_book;
+
+    // treasury bonds should not have matured
+    let now = Clock::get()?.unix_timestamp;
+    if treasury_bonds.has_matured(now) {
+        return Err(TreasuryBondsError::InvalidBondMaturityStatus.into());
+    }
+
+    // order_book
+    order_book.treasury_bonds = treasury_bonds.key();
+    order_book.next_order_id = 1;
+    order_book.is_initialized = true;
+    order_book.bump = ctx.bumps.order_book;
+
+    Ok(())
+}
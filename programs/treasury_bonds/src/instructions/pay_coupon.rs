@@ -0,0 +1,270 @@
+//! PayCoupon instruction handler
+
+use {
+    crate::{
+        error::TreasuryBondsError,
+        events::TaxYearClosed,
+        pricing::{civil_from_days, SECONDS_PER_DAY},
+        state::{
+            amortization_schedule::AmortizationSchedule,
+            coupon_schedule::CouponSchedule,
+            deposit_base::DepositBase,
+            holding::Holding,
+            investor::Investor,
+            tax_profile::TaxProfile,
+            tax_record::TaxRecord,
+            treasury_bonds::{TreasuryBonds, ZERO_COUPON_BOND},
+        },
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+    },
+};
+
+#[derive(Accounts)]
+pub struct PayCoupon<'info> {
+    #[account(has_one = deposit_account,
+        constraint = treasury_bonds.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        constraint = treasury_bonds.type_of_bond != ZERO_COUPON_BOND @ TreasuryBondsError::CouponNotApplicable,
+        seeds = [
+            b"treasury-bonds",
+            treasury_bonds.owner.as_ref(),
+            treasury_bonds.issue_no.as_bytes()
+        ],
+        bump = treasury_bonds.bump
+    )]
+    pub treasury_bonds: Account<'info, TreasuryBonds>,
+    #[account(mut, has_one = treasury_bonds,
+        constraint = coupon_schedule.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [b"coupon-schedule", treasury_bonds.key().as_ref()],
+        bump
+    )]
+    pub coupon_schedule: Account<'info, CouponSchedule>,
+    #[account(has_one = treasury_bonds,
+        constraint = amortization_schedule.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [b"amortization-schedule", treasury_bonds.key().as_ref()],
+        bump
+    )]
+    pub amortization_schedule: Box<Account<'info, AmortizationSchedule>>,
+    #[account(has_one = owner,
+        constraint = investor.active @ TreasuryBondsError::InvalidInvestorStatus
+    )]
+    pub investor: Account<'info, Investor>,
+    #[account(mut, has_one = investor, has_one = treasury_bonds,
+        seeds = [b"holding", investor.key().as_ref(), treasury_bonds.key().as_ref()],
+        bump = holding.bump
+    )]
+    pub holding: Account<'info, Holding>,
+    #[account(has_one = treasury_bonds,
+        constraint = tax_profile.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [b"tax-profile", treasury_bonds.key().as_ref()],
+        bump = tax_profile.bump
+    )]
+    pub tax_profile: Account<'info, TaxProfile>,
+    // withheld tax and coupons paid to the investor in the tax year, for year-end certificates
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + TaxRecord::INIT_SPACE,
+        seeds = [b"tax-record", investor.key().as_ref()],
+        bump
+    )]
+    pub tax_record: Account<'info, TaxRecord>,
+    // paid out of the treasury vault
+    #[account(mut,
+        constraint = sender_tokens.key() == treasury_bonds.vault_tokens @ TreasuryBondsError::InvalidVaultAccount
+    )]
+    pub sender_tokens: Account<'info, TokenAccount>,
+    #[account(mut,
+        constraint = recipient_tokens.mint == mint_token.key() @ TreasuryBondsError::InvalidSettlementMint,
+        constraint = recipient_tokens.owner == owner.key() @ TreasuryBondsError::InvalidTokenAccountOwner
+    )]
+    pub recipient_tokens: Account<'info, TokenAccount>,
+    #[account(mut,
+        address = tax_profile.tax_authority_tokens @ TreasuryBondsError::InvalidTaxAuthorityAccount
+    )]
+    pub tax_authority_tokens: Account<'info, TokenAccount>,
+    #[account(mut,
+        address = treasury_bonds.settlement_mint @ TreasuryBondsError::InvalidSettlementMint
+    )]
+    pub mint_token: Account<'info, Mint>,
+    #[account(mut,
+        constraint = deposit_account.is_initialized @ TreasuryBondsError::AccountNotInitialized
+    )]
+    pub deposit_account: Account<'info, DepositBase>,
+    #[account(seeds = [b"auth", deposit_account.key().as_ref()], bump)]
+    /// CHECK: no need to check this.
+    pub pda_auth: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"treasury-vault", pda_auth.key().as_ref()], bump)]
+    pub treasury_vault: SystemAccount<'info>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associate_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn pay_coupon(ctx: Context<PayCoupon>) -> Result<()> {
+    let treasury_bonds = &ctx.accounts.treasury_bonds;
+    let coupon_schedule = &mut ctx.accounts.coupon_schedule;
+    let amortization_schedule = &ctx.accounts.amortization_schedule;
+    let investor = &ctx.accounts.investor;
+    let holding = &mut ctx.accounts.holding;
+    let tax_profile = &ctx.accounts.tax_profile;
+    let tax_record = &mut ctx.accounts.tax_record;
+    let sender_tokens = &ctx.accounts.sender_tokens;
+    let recipient_tokens = &ctx.accounts.recipient_tokens;
+    let tax_authority_tokens = &ctx.accounts.tax_authority_tokens;
+    let mint_token = &ctx.accounts.mint_token;
+    let pda_auth = &mut ctx.accounts.pda_auth;
+    let treasury_vault = &mut ctx.accounts.treasury_vault;
+    let token_program = &ctx.accounts.token_program;
+    let coupon_rate = treasury_bonds.coupon_rate;
+    let available_funds: u32 = holding.available_funds;
+    let coupon_periods_paid = holding.coupon_periods_paid;
+    let decimals: u8 = treasury_bonds.decimals;
+
+    // investor's available funds should exceed zero
+    if available_funds == 0 {
+        return Err(TreasuryBondsError::InsufficientFunds.into());
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+
+    // withheld tax is recorded against the year the coupon is paid in
+    let (year, _, _) = civil_from_days(now.div_euclid(SECONDS_PER_DAY));
+    let tax_year =
+        u16::try_from(year).map_err(|_| TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    let periods_due = coupon_schedule.periods_due(now);
+
+    // only coupon periods that have fallen due and are not yet paid can be claimed
+    if periods_due <= coupon_periods_paid {
+        return Err(TreasuryBondsError::NoCouponDue.into());
+    }
+
+    let face_value = treasury_bonds
+        .face_value_of(available_funds)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    // coupon per period = face value * coupon rate * year fraction of the period,
+    // measured under the day-count convention of the treasury bonds. amortizing bonds
+    // pay coupons on the face value outstanding at the start of the period
+    let mut _amount: u64 = 0;
+    for period in (coupon_periods_paid + 1)..=periods_due {
+        let period_start = coupon_schedule
+            .coupon_date(period - 1)
+            .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+        let outstanding_face_value = AmortizationSchedule::share_of(
+            face_value,
+            amortization_schedule.outstanding_rate_at(period_start),
+        )
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+        let coupon = coupon_schedule
+            .coupon_of_period(
+                outstanding_face_value,
+                coupon_rate,
+                treasury_bonds.day_count,
+                period,
+            )
+            .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+        _amount = _amount
+            .checked_add(coupon)
+            .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+    }
+
+    // withholding tax is deducted from the coupon, infrastructure bonds are exempt
+    let tax_amount = tax_profile
+        .withholding_tax_of(_amount)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+    let net_amount = _amount
+        .checked_sub(tax_amount)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    // record the paid periods so that no coupon can be claimed twice
+    holding.coupon_periods_paid = periods_due;
+
+    holding.total_coupons_received = holding
+        .total_coupons_received
+        .checked_add(_amount)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    coupon_schedule.total_coupons_paid = coupon_schedule
+        .total_coupons_paid
+        .checked_add(_amount)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    // tax_record
+    if !tax_record.is_initialized {
+        tax_record.investor = investor.key();
+        tax_record.tax_year = tax_year;
+        tax_record.is_initialized = true;
+        tax_record.bump = ctx.bumps.tax_record;
+    }
+
+    // the first coupon paid in a new year closes the tax year recorded so far
+    if tax_record.tax_year != tax_year {
+        emit!(TaxYearClosed {
+            investor: investor.key(),
+            tax_year: tax_record.tax_year,
+            total_coupons: tax_record.total_coupons,
+            total_tax_withheld: tax_record.total_tax_withheld,
+        });
+
+        tax_record.tax_year = tax_year;
+        tax_record.total_coupons = 0;
+        tax_record.total_tax_withheld = 0;
+    }
+
+    tax_record.total_coupons = tax_record
+        .total_coupons
+        .checked_add(_amount)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    tax_record.total_tax_withheld = tax_record
+        .total_tax_withheld
+        .checked_add(tax_amount)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    let seeds = &[
+        b"treasury-vault",
+        pda_auth.to_account_info().key.as_ref(),
+        &[ctx.bumps.treasury_vault],
+    ];
+
+    let signer = &[&seeds[..]];
+
+    // Transfer coupon net of withholding tax from treasury vault to recipient
+    let cpi_accounts = TransferChecked {
+        from: sender_tokens.to_account_info(),
+        mint: mint_token.to_account_info(),
+        to: recipient_tokens.to_account_info(),
+        authority: treasury_vault.to_account_info(),
+    };
+
+    let cpi = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer);
+
+    transfer_checked(cpi, net_amount, decimals)?;
+
+    // Transfer withheld tax from treasury vault to the tax authority
+    if tax_amount > 0 {
+        let cpi_accounts = TransferChecked {
+            from: sender_tokens.to_account_info(),
+            mint: mint_token.to_account_info(),
+            to: tax_authority_tokens.to_account_info(),
+            authority: treasury_vault.to_account_info(),
+        };
+
+        let cpi =
+            CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer);
+
+        transfer_checked(cpi, tax_amount, decimals)?;
+    }
+
+    Ok(())
+}
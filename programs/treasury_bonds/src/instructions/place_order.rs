@@ -0,0 +1,315 @@
+//! PlaceOrder instruction handler
+
+use {
+    crate::{
+        bond_units::{freeze_bond_tokens, thaw_bond_tokens},
+        error::TreasuryBondsError,
+        events::OrderEvicted,
+        state::{
+            amortization_schedule::AmortizationSchedule,
+            coupon_schedule::CouponSchedule,
+            holding::Holding,
+            investor::Investor,
+            order::Order,
+            order_book::{OrderBook, ASK_ORDER, BID_ORDER},
+            treasury_bonds::{TreasuryBonds, ZERO_COUPON_BOND},
+        },
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token::{transfer, Mint, Token, TokenAccount, Transfer},
+        token_2022::spl_token_2022::onchain::invoke_transfer_checked,
+        token_interface::{self, TokenInterface},
+    },
+};
+
+#[derive(Accounts)]
+#[instruction(params: PlaceOrderParams)]
+pub struct PlaceOrder<'info> {
+    #[account(
+        constraint = treasury_bonds.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [
+            b"treasury-bonds",
+            treasury_bonds.owner.as_ref(),
+            treasury_bonds.issue_no.as_bytes()
+        ],
+        bump = treasury_bonds.bump
+    )]
+    pub treasury_bonds: Box<Account<'info, TreasuryBonds>>,
+    #[account(has_one = treasury_bonds,
+        constraint = coupon_schedule.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [b"coupon-schedule", treasury_bonds.key().as_ref()],
+        bump
+    )]
+    pub coupon_schedule: Box<Account<'info, CouponSchedule>>,
+    #[account(has_one = treasury_bonds,
+        constraint = amortization_schedule.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [b"amortization-schedule", treasury_bonds.key().as_ref()],
+        bump
+    )]
+    pub amortization_schedule: Box<Account<'info, AmortizationSchedule>>,
+    #[account(mut, has_one = treasury_bonds,
+        constraint = order_book.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [b"order-book", treasury_bonds.key().as_ref()],
+        bump = order_book.bump
+    )]
+    pub order_book: Box<Account<'info, OrderBook>>,
+    // init means to create account
+    // bump to use unique address for account
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Order::INIT_SPACE,
+        seeds = [
+            b"order",
+            order_book.key().as_ref(),
+            order_book.next_order_id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub order: Box<Account<'info, Order>>,
+    #[account(has_one = owner,
+        constraint = investor.active @ TreasuryBondsError::InvalidInvestorStatus
+    )]
+    pub investor: Box<Account<'info, Investor>>,
+    // holding is created on the investor's first order so that bids can be filled into it
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + Holding::INIT_SPACE,
+        seeds = [b"holding", investor.key().as_ref(), treasury_bonds.key().as_ref()],
+        bump
+    )]
+    pub holding: Box<Account<'info, Holding>>,
+    // settlement amount of bids is paid into escrow
+    #[account(mut,
+        constraint = sender_tokens.mint == mint_token.key() @ TreasuryBondsError::InvalidSettlementMint,
+        constraint = sender_tokens.owner == owner.key() @ TreasuryBondsError::InvalidTokenAccountOwner
+    )]
+    pub sender_tokens: Box<Account<'info, TokenAccount>>,
+    #[account(mut,
+        associated_token::mint = mint_token,
+        associated_token::authority = order_book
+    )]
+    pub escrow_tokens: Box<Account<'info, TokenAccount>>,
+    #[account(address = treasury_bonds.settlement_mint @ TreasuryBondsError::InvalidSettlementMint)]
+    pub mint_token: Box<Account<'info, Mint>>,
+    #[account(
+        address = treasury_bonds.bond_mint @ TreasuryBondsError::InvalidBondMint
+    )]
+    pub bond_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    // bond units of asks are paid into escrow, bids are filled into this token account
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = bond_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = bond_token_program
+    )]
+    pub bond_tokens: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    #[account(mut,
+        associated_token::mint = bond_mint,
+        associated_token::authority = order_book,
+        associated_token::token_program = bond_token_program
+    )]
+    pub escrow_bond_tokens: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub bond_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct PlaceOrderParams {
+    pub side: u8,         // bid (buy) or ask (sell) order
+    pub clean_price: u32, // clean price per 100 face value (2 decimals i.e 9850 = 98.50)
+    pub amount: u32,      // amount of treasury bonds to buy or sell
+}
+
+// places a bid or ask on the order book of the treasury bonds, escrowing the bid's
+// settlement amount or the ask's bond units until the order is matched or cancelled.
+// an order priced better than the worst order of a full side takes its place, the
+// evicted order keeping its escrow until it is cancelled.
+pub fn place_order<'info>(
+    ctx: Context<'_, '_, '_, 'info, PlaceOrder<'info>>,
+    params: &PlaceOrderParams,
+) -> Result<()> {
+    msg!("Validate inputs");
+    if params.side != BID_ORDER && params.side != ASK_ORDER {
+        return Err(TreasuryBondsError::InvalidOrderSide.into());
+    }
+
+    if params.clean_price == 0 {
+        return Err(TreasuryBondsError::InvalidOrderPrice.into());
+    }
+
+    if params.amount == 0 {
+        return Err(TreasuryBondsError::InvalidAmount.into());
+    }
+
+    let treasury_bonds = &ctx.accounts.treasury_bonds;
+    let coupon_schedule = &ctx.accounts.coupon_schedule;
+    let amortization_schedule = &ctx.accounts.amortization_schedule;
+    let order_book = &mut ctx.accounts.order_book;
+    let order = &mut ctx.accounts.order;
+    let investor = &ctx.accounts.investor;
+    let holding = &mut ctx.accounts.holding;
+    let available_funds: u32 = holding.available_funds;
+    let _amount = params.amount;
+
+    // treasury bonds should not have matured
+    let now = Clock::get()?.unix_timestamp;
+    if treasury_bonds.has_matured(now) {
+        return Err(TreasuryBondsError::InvalidBondMaturityStatus.into());
+    }
+
+    if params.side == BID_ORDER {
+        // bids should meet the minimum trade amount
+        if _amount < treasury_bonds.minimum_trade_amount {
+            return Err(TreasuryBondsError::InvalidTradeAmount.into());
+        }
+    } else {
+        // investor's(seller) available funds should cover the amount offered
+        if available_funds < _amount {
+            return Err(TreasuryBondsError::InsufficientFunds.into());
+        }
+
+        // partial sells should meet the minimum trade amount
+        if !treasury_bonds.is_valid_trade_amount(_amount, available_funds) {
+            return Err(TreasuryBondsError::InvalidTradeAmount.into());
+        }
+    }
+
+    // holding is created on the investor's first order of the treasury bonds
+    if !holding.is_initialized {
+        holding.investor = investor.key();
+        holding.treasury_bonds = treasury_bonds.key();
+        holding.is_initialized = true;
+        holding.bump = ctx.bumps.holding;
+    }
+
+    // order
+    order.order_book = order_book.key();
+    order.investor = investor.key();
+    order.owner = *ctx.accounts.owner.key;
+    order.order_id = order_book.next_order_id;
+    order.side = params.side;
+    order.clean_price = params.clean_price;
+    order.amount = _amount;
+    order.bump = ctx.bumps.order;
+
+    // order_book
+    // on a full side of the book, the order takes the place of the worst priced order
+    let evicted_order_id = order_book.add_order(order.side, order.order_id, order.clean_price)?;
+
+    if let Some(evicted_order_id) = evicted_order_id {
+        emit!(OrderEvicted {
+            treasury_bonds: treasury_bonds.key(),
+            order_id: evicted_order_id,
+            side: order.side,
+        });
+    }
+
+    order_book.next_order_id = order_book
+        .next_order_id
+        .checked_add(1)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    if params.side == BID_ORDER {
+        let face_value = treasury_bonds
+            .face_value_of(_amount)
+            .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+        // amortizing bonds trade on the face value still outstanding
+        let face_value = AmortizationSchedule::share_of(
+            face_value,
+            amortization_schedule.outstanding_rate_at(now),
+        )
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+        // bid's settlement amount at the bid price
+        let clean_amount = AmortizationSchedule::share_of(
+            treasury_bonds
+                .clean_value_of(_amount, params.clean_price)
+                .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?,
+            amortization_schedule.outstanding_rate_at(now),
+        )
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+        // bids are settled at the dirty price, so the escrow also covers the most coupon
+        // that can have accrued by the time the bid is matched
+        let accrued_coupon = if treasury_bonds.type_of_bond == ZERO_COUPON_BOND {
+            0
+        } else {
+            coupon_schedule
+                .max_accrued_coupon(
+                    face_value,
+                    treasury_bonds.coupon_rate,
+                    treasury_bonds.day_count,
+                    now,
+                )
+                .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?
+        };
+
+        let _amount = clean_amount
+            .checked_add(accrued_coupon)
+            .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+        order.escrowed_amount = _amount;
+
+        // Transfer settlement amount from investor to escrow
+        transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.sender_tokens.to_account_info(),
+                    to: ctx.accounts.escrow_tokens.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            _amount,
+        )?;
+    } else {
+        // Get unit_treasury_bonds from the product of unit_cost_of_treasury_bonds and _amount
+        let unit_treasury_bonds = treasury_bonds
+            .unit_cost_of_treasury_bonds
+            .checked_mul(_amount)
+            .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+        // Thaw the investor's bond units for the program to move them
+        thaw_bond_tokens(
+            &ctx.accounts.treasury_bonds,
+            &ctx.accounts.bond_mint,
+            &ctx.accounts.bond_tokens,
+            &ctx.accounts.bond_token_program,
+        )?;
+
+        // Transfer bond units from investor to escrow
+        // remaining accounts carry the transfer hook accounts of bond mints in Token-2022 mode
+        invoke_transfer_checked(
+            ctx.accounts.bond_token_program.key,
+            ctx.accounts.bond_tokens.to_account_info(),
+            ctx.accounts.bond_mint.to_account_info(),
+            ctx.accounts.escrow_bond_tokens.to_account_info(),
+            ctx.accounts.owner.to_account_info(),
+            ctx.remaining_accounts,
+            unit_treasury_bonds as u64,
+            ctx.accounts.bond_mint.decimals,
+            &[],
+        )?;
+
+        // Freeze the investor's bond units again
+        freeze_bond_tokens(
+            &ctx.accounts.treasury_bonds,
+            &ctx.accounts.bond_mint,
+            &ctx.accounts.bond_tokens,
+            &ctx.accounts.bond_token_program,
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,217 @@
+//! QuotePriceYield instruction handler
+
+use {
+    crate::{
+        error::TreasuryBondsError,
+        pricing::{
+            yield_to_maturity::{BondCashFlows, FIXED_POINT_SCALE},
+            BASIS_POINTS,
+        },
+        state::{
+            amortization_schedule::AmortizationSchedule,
+            coupon_schedule::{CouponSchedule, COUPON_PERIODS_PER_YEAR},
+            treasury_bonds::{TreasuryBonds, PAR_CLEAN_PRICE, ZERO_COUPON_BOND},
+        },
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct QuotePriceYield<'info> {
+    #[account(
+        constraint = treasury_bonds.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [
+            b"treasury-bonds",
+            treasury_bonds.owner.as_ref(),
+            treasury_bonds.issue_no.as_bytes()
+        ],
+        bump = treasury_bonds.bump
+    )]
+    pub treasury_bonds: Account<'info, TreasuryBonds>,
+    #[account(has_one = treasury_bonds,
+        constraint = coupon_schedule.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [b"coupon-schedule", treasury_bonds.key().as_ref()],
+        bump
+    )]
+    pub coupon_schedule: Account<'info, CouponSchedule>,
+    #[account(has_one = treasury_bonds,
+        constraint = amortization_schedule.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [b"amortization-schedule", treasury_bonds.key().as_ref()],
+        bump
+    )]
+    pub amortization_schedule: Box<Account<'info, AmortizationSchedule>>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct QuotePriceYieldParams {
+    pub clean_price: Option<u32>, // clean price per 100 face value (2 decimals) to get the yield of
+    pub yield_rate: Option<u32>,  // annual yield (basis points) to get the clean price of
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug)]
+pub struct PriceYieldQuote {
+    pub clean_price: u32,      // clean price per 100 face value (2 decimals)
+    pub dirty_price: u32,      // clean price plus accrued coupon per 100 face value (2 decimals)
+    pub accrued_interest: u32, // coupon accrued since the last coupon date per 100 face value (2 decimals)
+    pub yield_rate: u32,       // annual yield to maturity (basis points)
+    pub remaining_periods: u8, // coupon (discount, for zero coupon bonds) periods until maturity
+}
+
+// read-only quote of the yield to maturity at a clean price, or the clean price at a yield,
+// as at the current time. the quote is returned to the caller through return data.
+pub fn quote_price_yield(
+    ctx: Context<QuotePriceYield>,
+    params: &QuotePriceYieldParams,
+) -> Result<PriceYieldQuote> {
+    msg!("Validate inputs");
+    if params.clean_price.is_some() == params.yield_rate.is_some() {
+        return Err(TreasuryBondsError::InvalidPriceQuote.into());
+    }
+
+    let treasury_bonds = &ctx.accounts.treasury_bonds;
+    let coupon_schedule = &ctx.accounts.coupon_schedule;
+    let amortization_schedule = &ctx.accounts.amortization_schedule;
+
+    // treasury bonds should not have matured
+    let now = Clock::get()?.unix_timestamp;
+    if treasury_bonds.has_matured(now) {
+        return Err(TreasuryBondsError::InvalidBondMaturityStatus.into());
+    }
+
+    // zero coupon bonds have no coupon periods, they are discounted over the time left
+    // from the value and redemption dates
+    let (cash_flows, remaining_periods) = if treasury_bonds.type_of_bond == ZERO_COUPON_BOND {
+        let cash_flows = treasury_bonds
+            .zero_coupon_cash_flows(now)
+            .ok_or(TreasuryBondsError::PriceYieldNotSolved)?;
+        let remaining_periods = u8::try_from(cash_flows.amounts.len())
+            .map_err(|_| TreasuryBondsError::InvalidArithmeticOperation)?;
+
+        (cash_flows, remaining_periods)
+    } else {
+        let periods_due = coupon_schedule.periods_due(now);
+        let remaining_periods = coupon_schedule
+            .total_coupon_periods
+            .checked_sub(periods_due)
+            .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+        // fraction of the current coupon period elapsed
+        let period_start = coupon_schedule
+            .coupon_date(periods_due)
+            .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+        let period_end = coupon_schedule
+            .coupon_date(periods_due + 1)
+            .ok_or(TreasuryBondsError::PriceYieldNotSolved)?;
+        let period_length = period_end
+            .checked_sub(period_start)
+            .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+        let elapsed = now
+            .checked_sub(period_start)
+            .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?
+            .clamp(0, period_length);
+        let accrued_fraction = (elapsed as u128)
+            .checked_mul(FIXED_POINT_SCALE)
+            .and_then(|elapsed| elapsed.checked_div(period_length as u128))
+            .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+        // prices of amortizing bonds are quoted per 100 of the face value outstanding
+        let cash_flows = if !amortization_schedule.tranches.is_empty() {
+            let coupon_dates = ((periods_due + 1)..=coupon_schedule.total_coupon_periods)
+                .map(|period| coupon_schedule.coupon_date(period))
+                .collect::<Option<Vec<i64>>>()
+                .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+            let principal_repayments = amortization_schedule
+                .remaining_repayments(now, &coupon_dates)
+                .ok_or(TreasuryBondsError::PriceYieldNotSolved)?;
+
+            BondCashFlows::amortizing(
+                treasury_bonds.coupon_rate,
+                COUPON_PERIODS_PER_YEAR,
+                &principal_repayments,
+                accrued_fraction,
+            )
+        } else {
+            BondCashFlows::fixed_coupon(
+                treasury_bonds.coupon_rate,
+                COUPON_PERIODS_PER_YEAR,
+                remaining_periods as u32,
+                accrued_fraction,
+            )
+        }
+        .ok_or(TreasuryBondsError::PriceYieldNotSolved)?;
+
+        (cash_flows, remaining_periods)
+    };
+
+    let (clean_price, yield_rate) = match (params.clean_price, params.yield_rate) {
+        (Some(clean_price), _) => {
+            let clean_price = from_price(clean_price);
+            let yield_rate = cash_flows
+                .yield_from_clean_price(clean_price)
+                .ok_or(TreasuryBondsError::PriceYieldNotSolved)?;
+
+            (clean_price, yield_rate)
+        }
+        (_, Some(yield_rate)) => {
+            let yield_rate = from_basis_points(yield_rate);
+            let clean_price = cash_flows
+                .clean_price(yield_rate)
+                .ok_or(TreasuryBondsError::PriceYieldNotSolved)?;
+
+            (clean_price, yield_rate)
+        }
+        _ => return Err(TreasuryBondsError::InvalidPriceQuote.into()),
+    };
+
+    let accrued_interest = cash_flows
+        .accrued_interest()
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+    let dirty_price = clean_price
+        .checked_add(accrued_interest)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    let quote = PriceYieldQuote {
+        clean_price: to_price(clean_price)?,
+        dirty_price: to_price(dirty_price)?,
+        accrued_interest: to_price(accrued_interest)?,
+        yield_rate: to_basis_points(yield_rate)?,
+        remaining_periods,
+    };
+
+    msg!(
+        "clean price: {}, dirty price: {}, yield: {} bps",
+        quote.clean_price,
+        quote.dirty_price,
+        quote.yield_rate
+    );
+
+    Ok(quote)
+}
+
+// fixed-point price per unit of face value of a price per 100 face value (2 decimals)
+fn from_price(price: u32) -> u128 {
+    price as u128 * FIXED_POINT_SCALE / PAR_CLEAN_PRICE as u128
+}
+
+// fixed-point rate of a rate in basis points
+fn from_basis_points(rate: u32) -> u128 {
+    rate as u128 * FIXED_POINT_SCALE / BASIS_POINTS as u128
+}
+
+// price per 100 face value (2 decimals) of a fixed-point price per unit of face value (rounded)
+fn to_price(price: u128) -> Result<u32> {
+    to_scale(price, PAR_CLEAN_PRICE as u128)
+}
+
+// rate in basis points of a fixed-point rate (rounded)
+fn to_basis_points(rate: u128) -> Result<u32> {
+    to_scale(rate, BASIS_POINTS as u128)
+}
+
+fn to_scale(value: u128, scale: u128) -> Result<u32> {
+    value
+        .checked_mul(scale)
+        .and_then(|value| value.checked_add(FIXED_POINT_SCALE / 2))
+        .and_then(|value| u32::try_from(value / FIXED_POINT_SCALE).ok())
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation.into())
+}
@@ -2,33 +2,72 @@
 
 use {
     crate::{
+        bond_units::{freeze_bond_tokens, thaw_bond_tokens},
         error::TreasuryBondsError,
-        state::{deposit_base::DepositBase, investor::Investor, treasury_bonds::TreasuryBonds},
+        state::{
+            amortization_schedule::AmortizationSchedule,
+            coupon_schedule::CouponSchedule,
+            deposit_base::DepositBase,
+            holding::Holding,
+            investor::Investor,
+            treasury_bonds::{TreasuryBonds, ZERO_COUPON_BOND},
+        },
     },
     anchor_lang::prelude::*,
     anchor_spl::{
         associated_token::AssociatedToken,
         token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+        token_interface::{self, TokenInterface},
     },
 };
 
 #[derive(Accounts)]
 #[instruction(params: RedeemTreasuryBondsParams)]
 pub struct RedeemTreasuryBonds<'info> {
-    #[account(mut,
+    #[account(mut, has_one = deposit_account,
         constraint = treasury_bonds.is_initialized @ TreasuryBondsError::AccountNotInitialized,
-        constraint = treasury_bonds.is_matured @ TreasuryBondsError::InvalidBondMaturityStatus
+        seeds = [
+            b"treasury-bonds",
+            treasury_bonds.owner.as_ref(),
+            treasury_bonds.issue_no.as_bytes()
+        ],
+        bump = treasury_bonds.bump
     )]
     pub treasury_bonds: Account<'info, TreasuryBonds>,
-    #[account(mut,has_one = owner,
+    #[account(has_one = treasury_bonds,
+        constraint = coupon_schedule.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [b"coupon-schedule", treasury_bonds.key().as_ref()],
+        bump
+    )]
+    pub coupon_schedule: Account<'info, CouponSchedule>,
+    #[account(has_one = treasury_bonds,
+        constraint = amortization_schedule.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [b"amortization-schedule", treasury_bonds.key().as_ref()],
+        bump
+    )]
+    pub amortization_schedule: Box<Account<'info, AmortizationSchedule>>,
+    #[account(has_one = owner,
         constraint = investor.active @ TreasuryBondsError::InvalidInvestorStatus
     )]
     pub investor: Account<'info, Investor>,
-    #[account(mut)]
+    #[account(mut, has_one = investor, has_one = treasury_bonds,
+        seeds = [b"holding", investor.key().as_ref(), treasury_bonds.key().as_ref()],
+        bump = holding.bump
+    )]
+    pub holding: Account<'info, Holding>,
+    // paid out of the treasury vault
+    #[account(mut,
+        constraint = sender_tokens.key() == treasury_bonds.vault_tokens @ TreasuryBondsError::InvalidVaultAccount
+    )]
     pub sender_tokens: Account<'info, TokenAccount>,
-    #[account(mut)]
+    #[account(mut,
+        constraint = recipient_tokens.mint == mint_token.key() @ TreasuryBondsError::InvalidSettlementMint,
+        constraint = recipient_tokens.owner == owner.key() @ TreasuryBondsError::InvalidTokenAccountOwner
+    )]
     pub recipient_tokens: Account<'info, TokenAccount>,
-    #[account(mut)]
+    #[account(mut,
+        address = treasury_bonds.settlement_mint @ TreasuryBondsError::InvalidSettlementMint
+    )]
     pub mint_token: Account<'info, Mint>,
     #[account(mut,
         constraint = deposit_account.is_initialized @ TreasuryBondsError::AccountNotInitialized
@@ -39,10 +78,22 @@ pub struct RedeemTreasuryBonds<'info> {
     pub pda_auth: UncheckedAccount<'info>,
     #[account(mut, seeds = [b"treasury-vault", pda_auth.key().as_ref()], bump)]
     pub treasury_vault: SystemAccount<'info>,
+    #[account(mut,
+        address = treasury_bonds.bond_mint @ TreasuryBondsError::InvalidBondMint
+    )]
+    pub bond_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    // redeemed bond units are burnt from the investor's token account
+    #[account(mut,
+        associated_token::mint = bond_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = bond_token_program
+    )]
+    pub bond_tokens: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
     // mut makes it changeble (mutable)
     #[account(mut)]
     pub owner: Signer<'info>,
     pub token_program: Program<'info, Token>,
+    pub bond_token_program: Interface<'info, TokenInterface>,
     pub associate_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
@@ -62,30 +113,51 @@ pub fn redeem_treasury_bonds(
     }
 
     let treasury_bonds = &mut ctx.accounts.treasury_bonds;
-    let investor = &mut ctx.accounts.investor;
+    let coupon_schedule = &ctx.accounts.coupon_schedule;
+    let amortization_schedule = &ctx.accounts.amortization_schedule;
+    let holding = &mut ctx.accounts.holding;
     let sender_tokens = &ctx.accounts.sender_tokens;
     let recipient_tokens = &ctx.accounts.recipient_tokens;
     let mint_token = &ctx.accounts.mint_token;
-    let deposit_account = &ctx.accounts.deposit_account;
     let pda_auth = &mut ctx.accounts.pda_auth;
     let treasury_vault = &mut ctx.accounts.treasury_vault;
     let token_program = &ctx.accounts.token_program;
+    let bond_mint = &ctx.accounts.bond_mint;
+    let bond_tokens = &ctx.accounts.bond_tokens;
+    let bond_token_program = &ctx.accounts.bond_token_program;
     let unit_cost_of_treasury_bonds: u32 = treasury_bonds.unit_cost_of_treasury_bonds;
     let total_available_funds = treasury_bonds.total_available_funds;
-    let total_units_treasury_bonds: u32 = investor.total_units_treasury_bonds;
-    let available_funds: u32 = investor.available_funds;
+    let total_units_treasury_bonds: u32 = holding.total_units_treasury_bonds;
+    let available_funds: u32 = holding.available_funds;
     let decimals: u8 = treasury_bonds.decimals;
     let _amount = params.amount;
 
+    // treasury bonds should have matured, unless the issue allows early redemption
+    let now = Clock::get()?.unix_timestamp;
+    let is_early_redemption = !treasury_bonds.has_matured(now);
+    if is_early_redemption && !treasury_bonds.allows_early_redemption {
+        return Err(TreasuryBondsError::EarlyRedemptionNotAllowed.into());
+    }
+
     // investor's available funds should exceed zero
     if available_funds == 0 {
         return Err(TreasuryBondsError::InsufficientFunds.into());
     }
 
-    // investor's available funds should match transfer amount
-    if available_funds == _amount {
-    } else {
-        return Err(TreasuryBondsError::MismatchedAmount.into());
+    // coupons and principal tranches that have fallen due should be paid before redeeming
+    // the balance, otherwise they would be lost on the units redeemed
+    let periods_due = coupon_schedule.periods_due(now);
+    let tranches_due = amortization_schedule.tranches_due(now);
+    holding.verify_settled(periods_due, tranches_due)?;
+
+    // investor's available funds should cover the transfer amount
+    if available_funds < _amount {
+        return Err(TreasuryBondsError::InsufficientFunds.into());
+    }
+
+    // partial redemptions should meet the minimum trade amount
+    if !treasury_bonds.is_valid_trade_amount(_amount, available_funds) {
+        return Err(TreasuryBondsError::InvalidTradeAmount.into());
     }
 
     // treasury's available funds should exceed(or equal) transfer amount
@@ -99,30 +171,101 @@ pub fn redeem_treasury_bonds(
         .checked_mul(_amount)
         .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
 
+    // Deduct redeemed treasury bonds' cost basis from holding's cost_basis
+    let cost_basis = holding
+        .cost_basis_of(_amount)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+    holding.cost_basis = holding
+        .cost_basis
+        .checked_sub(cost_basis)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
     // Deduct sold unit_cost_of_treasury_bonds from investor's total_units_treasury_bonds
-    investor.total_units_treasury_bonds = total_units_treasury_bonds
+    holding.total_units_treasury_bonds = total_units_treasury_bonds
         .checked_sub(unit_cost_of_treasury_bonds)
         .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
 
     // Deduct actual_amount(sold unit_cost_of_treasury_bonds) from investor's available funds
-    investor.available_funds = available_funds
+    holding.available_funds = available_funds
         .checked_sub(_amount)
         .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
 
+    // A bondholder with no available funds left is removed from the holders count
+    if holding.available_funds == 0 {
+        treasury_bonds.holders_count = treasury_bonds
+            .holders_count
+            .checked_sub(1)
+            .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+    }
+
     // Deduct actual_amount(sold unit_cost_of_treasury_bonds) from total_available_funds
     treasury_bonds.total_available_funds = total_available_funds
         .checked_sub(_amount)
         .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
 
-    let base: u32 = 10;
-    let exponent = treasury_bonds.decimals as u32;
-    // lets get the amount in decimal format
-    // 10 ** 9 * 3(base 10, 9 decimals, 3 amount), // 3 amount of token to transfer (in smallest unit i.e 9 decimals)
-    let result = (base).pow(exponent);
-    let _amount = (_amount as u64)
-        .checked_mul(result as u64)
+    // Thaw the investor's bond units for the program to burn them
+    thaw_bond_tokens(
+        treasury_bonds,
+        bond_mint,
+        bond_tokens,
+        &ctx.accounts.bond_token_program,
+    )?;
+
+    // Burn the redeemed bond units
+    token_interface::burn(
+        CpiContext::new(
+            bond_token_program.to_account_info(),
+            token_interface::Burn {
+                mint: bond_mint.to_account_info(),
+                from: bond_tokens.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        unit_cost_of_treasury_bonds as u64,
+    )?;
+
+    // Freeze the investor's bond units again
+    freeze_bond_tokens(
+        treasury_bonds,
+        bond_mint,
+        bond_tokens,
+        &ctx.accounts.bond_token_program,
+    )?;
+
+    let face_value = treasury_bonds
+        .face_value_of(_amount)
         .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
 
+    // face value of amortizing bonds outstanding after the principal tranches repaid
+    let _amount = AmortizationSchedule::share_of(
+        face_value,
+        amortization_schedule.outstanding_rate(tranches_due),
+    )
+    .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    // treasury bonds redeemed before maturity are repaid at cost i.e the price paid for them,
+    // plus the coupon accrued since the last coupon date. face value is only repaid at maturity
+    let _amount = if is_early_redemption {
+        let accrued_coupon = if treasury_bonds.type_of_bond == ZERO_COUPON_BOND {
+            0
+        } else {
+            coupon_schedule
+                .accrued_coupon(
+                    _amount,
+                    treasury_bonds.coupon_rate,
+                    treasury_bonds.day_count,
+                    now,
+                )
+                .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?
+        };
+
+        cost_basis
+            .checked_add(accrued_coupon)
+            .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?
+    } else {
+        _amount
+    };
+
     // Transfer funds from treasury vault to recipient
     let cpi_accounts = TransferChecked {
         from: sender_tokens.to_account_info(),
@@ -134,7 +277,7 @@ pub fn redeem_treasury_bonds(
     let seeds = &[
         b"treasury-vault",
         pda_auth.to_account_info().key.as_ref(),
-        &[deposit_account.admin_treasury_vault_bump.unwrap()],
+        &[ctx.bumps.treasury_vault],
     ];
 
     let signer = &[&seeds[..]];
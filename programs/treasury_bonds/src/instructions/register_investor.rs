@@ -42,16 +42,12 @@ pub fn register_investor(
 ) -> Result<()> {
     // validate inputs
     msg!("Validate inputs");
-    if params.full_names.as_bytes().len() > 0
-        && params.full_names.as_bytes().len() <= FULL_NAMES_LENGTH
-    {
+    if !params.full_names.is_empty() && params.full_names.len() <= FULL_NAMES_LENGTH {
     } else {
         return Err(TreasuryBondsError::InvalidFullNamesLength.into());
     }
 
-    if params.country.as_bytes().len() != COUNTRY_LENGTH
-        && params.country.as_bytes().len() != COUNTRY_LENGTH_2
-    {
+    if params.country.len() != COUNTRY_LENGTH && params.country.len() != COUNTRY_LENGTH_2 {
         return Err(TreasuryBondsError::InvalidCountryLength.into());
     }
 
@@ -3,21 +3,50 @@
 use {
     crate::{
         error::TreasuryBondsError,
+        instructions::transfer_hook::extra_account_metas,
+        pricing::{is_valid_day_count, COUPON_RATE_SCALE},
         state::{
-            bond_issuer::BondIssuer, configs::TreasuryBondsConfigs, deposit_base::DepositBase,
-            treasury_bonds::TreasuryBonds,
+            amortization_schedule::{AmortizationSchedule, PrincipalTranche},
+            bond_issuer::BondIssuer,
+            configs::{TreasuryBondsConfigs, ISSUANCE_OPERATOR},
+            coupon_schedule::CouponSchedule,
+            deposit_base::DepositBase,
+            proposal::{Proposal, REGISTER_TREASURY_BONDS},
+            treasury_bonds::{TreasuryBonds, INFRASTRUCTURE_BOND, ZERO_COUPON_BOND},
         },
     },
-    anchor_lang::prelude::*,
+    anchor_lang::{prelude::*, system_program},
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token::{Mint, Token, TokenAccount},
+        token_2022::{
+            self,
+            spl_token_2022::{self, extension::ExtensionType, solana_program::program_pack::Pack},
+        },
+        token_interface::{self, TokenInterface},
+    },
+    spl_tlv_account_resolution::state::ExtraAccountMetaList,
+    spl_transfer_hook_interface::instruction::ExecuteInstruction,
 };
 
 #[derive(Accounts)]
 #[instruction(params: RegisterTreasuryBondsParams)]
 pub struct RegisterTreasuryBonds<'info> {
-    #[account(
-        mut, constraint = treasury_bonds_configs.is_initialized @ TreasuryBondsError::AccountNotInitialized
+    #[account(mut,
+        constraint = treasury_bonds_configs.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        constraint = treasury_bonds_configs.has_role(ISSUANCE_OPERATOR, &owner.key()) @ TreasuryBondsError::MissingRole,
+        seeds = [b"treasury-bonds-configs"],
+        bump
     )]
     pub treasury_bonds_configs: Account<'info, TreasuryBondsConfigs>,
+    // registration should be approved by the approvers
+    #[account(mut,
+        constraint = !proposal.is_executed @ TreasuryBondsError::ProposalAlreadyExecuted,
+        constraint = proposal.is_approved(&treasury_bonds_configs) @ TreasuryBondsError::ProposalNotApproved,
+        seeds = [b"proposal", proposal.proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
     // init means to create account
     // bump to use unique address for account
     #[account(
@@ -25,10 +54,27 @@ pub struct RegisterTreasuryBonds<'info> {
         payer = owner,
         space = 8 + TreasuryBonds::INIT_SPACE,
         constraint = !treasury_bonds.is_initialized @ TreasuryBondsError::AccountAlreadyInitialized,
-        seeds = [b"treasury-bonds", owner.key().as_ref()],
+        seeds = [b"treasury-bonds", owner.key().as_ref(), params.issue_no.as_bytes()],
+        bump
+    )]
+    pub treasury_bonds: Box<Account<'info, TreasuryBonds>>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + CouponSchedule::INIT_SPACE,
+        constraint = !coupon_schedule.is_initialized @ TreasuryBondsError::AccountAlreadyInitialized,
+        seeds = [b"coupon-schedule", treasury_bonds.key().as_ref()],
         bump
     )]
-    pub treasury_bonds: Account<'info, TreasuryBonds>,
+    pub coupon_schedule: Account<'info, CouponSchedule>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + AmortizationSchedule::INIT_SPACE,
+        seeds = [b"amortization-schedule", treasury_bonds.key().as_ref()],
+        bump
+    )]
+    pub amortization_schedule: Box<Account<'info, AmortizationSchedule>>,
     #[account(init, payer = owner, space = 8 + DepositBase::INIT_SPACE,
         constraint = !deposit_account.is_initialized @ TreasuryBondsError::AccountAlreadyInitialized
     )]
@@ -38,26 +84,55 @@ pub struct RegisterTreasuryBonds<'info> {
     pub pda_auth: UncheckedAccount<'info>,
     #[account(mut, seeds = [b"treasury-vault", pda_auth.key().as_ref()], bump)]
     pub treasury_vault: SystemAccount<'info>,
+    // token account of the treasury vault i.e receives purchase proceeds and pays out redemptions
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = mint_token,
+        associated_token::authority = treasury_vault
+    )]
+    pub vault_tokens: Box<Account<'info, TokenAccount>>,
+    // settlement mint of the treasury bonds i.e USDC or a configured stablecoin
+    #[account(
+        constraint = treasury_bonds_configs.is_settlement_mint(&mint_token.key()) @ TreasuryBondsError::SettlementMintNotAllowed
+    )]
+    pub mint_token: Box<Account<'info, Mint>>,
+    // mint of the bond units, minted to investors on purchase and burnt on redemption
+    #[account(mut, seeds = [b"bond-mint", treasury_bonds.key().as_ref()], bump)]
+    /// CHECK: created in the instruction handler, with a transfer hook in Token-2022 mode.
+    pub bond_mint: UncheckedAccount<'info>,
+    // investor accounts checked by the transfer hook (Token-2022 mode only)
+    #[account(mut, seeds = [b"extra-account-metas", bond_mint.key().as_ref()], bump)]
+    /// CHECK: created in the instruction handler in Token-2022 mode.
+    pub extra_account_meta_list: UncheckedAccount<'info>,
     // mut makes it changeble (mutable)
     #[account(mut)]
     pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub bond_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct RegisterTreasuryBondsParams {
-    issuer: BondIssuer,               // bond issuer details
-    country: String,                  // home country where treasury bonds is issued
-    issue_no: String,                 // issue no of bond
-    type_of_bond: u8, // type of bond i.e Fixed coupon Treasury bonds, Infrastructure bonds
-    tenor: u8,        // maturity period i.e between 2-30 years
-    coupon_rate: u8,  // coupon rate (%)
+    issuer: BondIssuer,                           // bond issuer details
+    country: String,                              // home country where treasury bonds is issued
+    issue_no: String,                             // issue no of bond
+    type_of_bond: u8, // type of bond i.e Fixed coupon Treasury bonds, Infrastructure bonds, Zero coupon bonds
+    tenor: u8,        // maturity period i.e between 2-30 years (1-30 years for zero coupon bonds)
+    coupon_rate: u32, // coupon rate in ten-thousandths of a percent i.e 129_655 = 12.9655%
     total_amounts_offered: u32, // total amounts offered for the given bond
     minimum_bid_amount: u32, // minimum bid amount
+    minimum_trade_amount: u32, // minimum amount of a partial sell or redemption i.e zero defaults to the minimum bid amount
+    allows_early_redemption: bool, // can investors redeem the treasury bonds before maturity
+    day_count: u8, // day-count convention of coupons and accrued interest i.e Actual/365, Actual/Actual (ICMA), 30/360
     unit_cost_of_treasury_bonds: u32, // unit cost of treasury bonds
-    decimals: u8,     // decimals for the token mint
-    value_date: String, // value date of bond
-    redemption_date: String, // redemption date of bond
+    decimals: u8,  // decimals for the token mint
+    value_date: i64, // value date of bond (unix timestamp)
+    redemption_date: i64, // redemption date of bond (unix timestamp)
+    transfer_hook: bool, // restrict transfers of the bond units to registered investors (Token-2022 only)
+    amortization_schedule: Vec<PrincipalTranche>, // principal repaid before maturity (infrastructure bonds only), empty for bullet bonds
 }
 
 // issuer length
@@ -67,8 +142,7 @@ const ISSUER_NO_LENGTH: usize = 20;
 // tenor length
 const TENOR_LENGTH: u8 = 2;
 const TENOR_LENGTH_2: u8 = 30;
-// date length
-const DATE_LENGTH: usize = 20;
+const ZERO_COUPON_TENOR_LENGTH: u8 = 1;
 // country length
 const COUNTRY_LENGTH: usize = 3;
 const COUNTRY_LENGTH_2: usize = 2;
@@ -79,46 +153,51 @@ pub fn register_treasury_bonds(
 ) -> Result<()> {
     // validate inputs
     msg!("Validate inputs");
-    if params.issuer.issuer.as_bytes().len() > 0
-        && params.issuer.issuer.as_bytes().len() <= ISSUER_LENGTH
-    {
+    if !params.issuer.issuer.is_empty() && params.issuer.issuer.len() <= ISSUER_LENGTH {
     } else {
         return Err(TreasuryBondsError::InvalidIssuerLength.into());
     }
 
-    if params.country.as_bytes().len() != COUNTRY_LENGTH
-        && params.country.as_bytes().len() != COUNTRY_LENGTH_2
-    {
+    if params.country.len() != COUNTRY_LENGTH && params.country.len() != COUNTRY_LENGTH_2 {
         return Err(TreasuryBondsError::InvalidCountryLength.into());
     }
 
-    if params.issue_no.as_bytes().len() > 0 && params.issue_no.as_bytes().len() <= ISSUER_NO_LENGTH
-    {
+    if !params.issue_no.is_empty() && params.issue_no.len() <= ISSUER_NO_LENGTH {
     } else {
         return Err(TreasuryBondsError::InvalidIssuerNoLength.into());
     }
 
     // 1 - Fixed coupon Treasury bonds
     // 2 - Infrastructure bonds
+    // 3 - Zero coupon bonds
 
-    let is_valid_bond_type = {
-        match params.type_of_bond {
-            1 | 2 => true,
-            _ => false,
-        }
-    };
+    let is_valid_bond_type = matches!(params.type_of_bond, 1..=3);
 
     if !is_valid_bond_type {
         return Err(TreasuryBondsError::InvalidTypeOfBond.into());
     }
 
+    let is_zero_coupon_bond = params.type_of_bond == ZERO_COUPON_BOND;
+
     // T-Bonds with maturities of between 2-30 years
-    if params.tenor >= TENOR_LENGTH && params.tenor <= TENOR_LENGTH_2 {
+    // Zero coupon bonds with maturities of between 1-30 years
+    let minimum_tenor = if is_zero_coupon_bond {
+        ZERO_COUPON_TENOR_LENGTH
+    } else {
+        TENOR_LENGTH
+    };
+
+    if params.tenor >= minimum_tenor && params.tenor <= TENOR_LENGTH_2 {
     } else {
         return Err(TreasuryBondsError::InvalidBondTenor.into());
     }
 
-    if params.coupon_rate > 0 {
+    // Zero coupon bonds pay no coupon, they are issued at a discount instead
+    if is_zero_coupon_bond {
+        if params.coupon_rate != 0 {
+            return Err(TreasuryBondsError::InvalidBondCouponRate.into());
+        }
+    } else if params.coupon_rate > 0 && params.coupon_rate as u64 <= COUPON_RATE_SCALE {
     } else {
         return Err(TreasuryBondsError::InvalidBondCouponRate.into());
     }
@@ -133,29 +212,88 @@ pub fn register_treasury_bonds(
         return Err(TreasuryBondsError::InvalidAmount.into());
     }
 
+    // partial trades should not fall below the minimum bid amount
+    let minimum_trade_amount = if params.minimum_trade_amount == 0 {
+        params.minimum_bid_amount
+    } else {
+        params.minimum_trade_amount
+    };
+
+    if minimum_trade_amount < params.minimum_bid_amount {
+        return Err(TreasuryBondsError::InvalidTradeAmount.into());
+    }
+
+    if !is_valid_day_count(params.day_count) {
+        return Err(TreasuryBondsError::InvalidDayCount.into());
+    }
+
     if params.unit_cost_of_treasury_bonds > 0 {
     } else {
         return Err(TreasuryBondsError::InvalidAmount.into());
     }
 
-    if params.value_date.as_bytes().len() > 0 && params.value_date.as_bytes().len() <= DATE_LENGTH {
+    if params.value_date <= 0 {
+        return Err(TreasuryBondsError::InvalidValueDate.into());
+    }
+
+    // redemption date should fall tenor years after the value date
+    if !TreasuryBonds::is_valid_term(params.value_date, params.redemption_date, params.tenor) {
+        return Err(TreasuryBondsError::InvalidRedemptionDate.into());
+    }
+
+    // infrastructure bonds may repay principal in tranches before maturity
+    if !params.amortization_schedule.is_empty() && params.type_of_bond != INFRASTRUCTURE_BOND {
+        return Err(TreasuryBondsError::InvalidAmortizationSchedule.into());
+    }
+
+    if AmortizationSchedule::is_valid_schedule(
+        &params.amortization_schedule,
+        params.value_date,
+        params.redemption_date,
+    ) {
     } else {
-        return Err(TreasuryBondsError::InvalidValueDateLength.into());
+        return Err(TreasuryBondsError::InvalidAmortizationSchedule.into());
     }
 
-    if params.redemption_date.as_bytes().len() > 0
-        && params.redemption_date.as_bytes().len() <= DATE_LENGTH
-    {
+    // action should be authorized by an approved proposal
+    let proposal = &mut ctx.accounts.proposal;
+    let now = Clock::get()?.unix_timestamp;
+    if proposal.has_expired(now) {
+        return Err(TreasuryBondsError::ProposalExpired.into());
+    }
+
+    if proposal.authorizes(
+        REGISTER_TREASURY_BONDS,
+        &ctx.accounts.treasury_bonds.key(),
+        &params.try_to_vec()?,
+    ) {
     } else {
-        return Err(TreasuryBondsError::InvalidValueRedemptionLength.into());
+        return Err(TreasuryBondsError::ProposalMismatch.into());
     }
 
+    proposal.is_executed = true;
+
     if params.decimals == 0 {
         return Err(TreasuryBondsError::InvalidNumeric.into());
     }
 
+    // amounts are converted using the decimals of the settlement mint
+    if params.decimals != ctx.accounts.mint_token.decimals {
+        return Err(TreasuryBondsError::InvalidNumeric.into());
+    }
+
+    // transfer hooks are a Token-2022 extension
+    if params.transfer_hook && ctx.accounts.bond_token_program.key() != token_2022::ID {
+        return Err(TreasuryBondsError::InvalidBondTokenProgram.into());
+    }
+
     let deposit_account = &mut ctx.accounts.deposit_account;
+    let vault_tokens = &ctx.accounts.vault_tokens;
+    let mint_token = &ctx.accounts.mint_token;
+    let bond_mint = &ctx.accounts.bond_mint;
     let treasury_bonds = &mut ctx.accounts.treasury_bonds;
+    let coupon_schedule = &mut ctx.accounts.coupon_schedule;
+    let amortization_schedule = &mut ctx.accounts.amortization_schedule;
     let treasury_bonds_configs = &mut ctx.accounts.treasury_bonds_configs;
 
     // deposit account
@@ -173,20 +311,164 @@ pub fn register_treasury_bonds(
     treasury_bonds.type_of_bond = params.type_of_bond;
     treasury_bonds.tenor = params.tenor;
     treasury_bonds.coupon_rate = params.coupon_rate;
+    // zero coupon bonds are discounted at the yield of their tenor
+    treasury_bonds.discount_rate = if is_zero_coupon_bond {
+        TreasuryBonds::zero_coupon_discount_rate(params.tenor)
+    } else {
+        0
+    };
     treasury_bonds.total_amounts_offered = params.total_amounts_offered;
     treasury_bonds.minimum_bid_amount = params.minimum_bid_amount;
+    treasury_bonds.minimum_trade_amount = minimum_trade_amount;
+    treasury_bonds.allows_early_redemption = params.allows_early_redemption;
+    treasury_bonds.day_count = params.day_count;
     treasury_bonds.is_initialized = true;
     treasury_bonds.unit_cost_of_treasury_bonds = params.unit_cost_of_treasury_bonds;
     treasury_bonds.decimals = params.decimals;
-    treasury_bonds.value_date = params.value_date.to_string();
-    treasury_bonds.redemption_date = params.redemption_date.to_string();
+    treasury_bonds.value_date = params.value_date;
+    treasury_bonds.redemption_date = params.redemption_date;
+    treasury_bonds.deposit_account = deposit_account.key();
+    treasury_bonds.settlement_mint = mint_token.key();
+    treasury_bonds.bond_mint = bond_mint.key();
+    treasury_bonds.vault_tokens = vault_tokens.key();
+    treasury_bonds.bump = ctx.bumps.treasury_bonds;
+
+    // coupon_schedule
+    // coupons are paid every six months throughout the bond's maturity, the last on the redemption date
+    // zero coupon bonds have no coupon periods
+    coupon_schedule.treasury_bonds = treasury_bonds.key();
+    coupon_schedule.maturity_date = params.redemption_date;
+    if !is_zero_coupon_bond {
+        coupon_schedule.total_coupon_periods = params
+            .tenor
+            .checked_mul(2)
+            .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+    }
+    coupon_schedule.is_initialized = true;
+
+    // amortization_schedule
+    // the face value outstanding after the last tranche is repaid at maturity
+    amortization_schedule.treasury_bonds = treasury_bonds.key();
+    amortization_schedule.tranches = params.amortization_schedule.to_vec();
+    amortization_schedule.is_initialized = true;
 
     let bond_issuer = BondIssuer {
         issuer: params.issuer.issuer.to_string(),
     };
 
     // treasury_bonds_configs
-    treasury_bonds_configs.issuers.push(bond_issuer);
+    // an issuer is listed once regardless of the number of bond issues registered
+    let is_listed_issuer = treasury_bonds_configs
+        .issuers
+        .iter()
+        .any(|x| x.issuer == bond_issuer.issuer);
+
+    if !is_listed_issuer {
+        treasury_bonds_configs.issuers.push(bond_issuer);
+    }
+
+    let treasury_bonds_key = treasury_bonds.key();
+    let bond_mint_key = bond_mint.key();
+    let owner = &ctx.accounts.owner;
+    let bond_token_program = &ctx.accounts.bond_token_program;
+    let system_program = &ctx.accounts.system_program;
+    let rent = Rent::get()?;
+
+    // bond mint
+    // in Token-2022 mode the mint carries a transfer hook pointing back to this program
+    let bond_mint_space = if params.transfer_hook {
+        ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&[
+            ExtensionType::TransferHook,
+        ])?
+    } else {
+        spl_token_2022::state::Mint::LEN
+    };
+
+    let seeds = &[
+        b"bond-mint",
+        treasury_bonds_key.as_ref(),
+        &[ctx.bumps.bond_mint],
+    ];
+
+    let signer = &[&seeds[..]];
+
+    system_program::create_account(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            system_program::CreateAccount {
+                from: owner.to_account_info(),
+                to: bond_mint.to_account_info(),
+            },
+            signer,
+        ),
+        rent.minimum_balance(bond_mint_space),
+        bond_mint_space as u64,
+        bond_token_program.key,
+    )?;
+
+    if params.transfer_hook {
+        token_interface::transfer_hook_initialize(
+            CpiContext::new(
+                bond_token_program.to_account_info(),
+                token_interface::TransferHookInitialize {
+                    token_program_id: bond_token_program.to_account_info(),
+                    mint: bond_mint.to_account_info(),
+                },
+            ),
+            Some(treasury_bonds_key),
+            Some(crate::ID),
+        )?;
+    }
+
+    // bond units are whole units of the treasury bonds i.e zero decimals,
+    // the treasury bonds freeze the investors' token accounts (see bond_units)
+    token_interface::initialize_mint2(
+        CpiContext::new(
+            bond_token_program.to_account_info(),
+            token_interface::InitializeMint2 {
+                mint: bond_mint.to_account_info(),
+            },
+        ),
+        0,
+        &treasury_bonds_key,
+        Some(&treasury_bonds_key),
+    )?;
+
+    // extra account metas
+    // investor accounts the token program passes to the transfer hook
+    if params.transfer_hook {
+        let extra_account_meta_list = &ctx.accounts.extra_account_meta_list;
+        let extra_account_metas = extra_account_metas()?;
+        let extra_account_meta_list_space =
+            ExtraAccountMetaList::size_of(extra_account_metas.len())?;
+
+        let seeds = &[
+            b"extra-account-metas",
+            bond_mint_key.as_ref(),
+            &[ctx.bumps.extra_account_meta_list],
+        ];
+
+        let signer = &[&seeds[..]];
+
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                system_program::CreateAccount {
+                    from: owner.to_account_info(),
+                    to: extra_account_meta_list.to_account_info(),
+                },
+                signer,
+            ),
+            rent.minimum_balance(extra_account_meta_list_space),
+            extra_account_meta_list_space as u64,
+            &crate::ID,
+        )?;
+
+        ExtraAccountMetaList::init::<ExecuteInstruction>(
+            &mut extra_account_meta_list.try_borrow_mut_data()?,
+            &extra_account_metas,
+        )?;
+    }
 
     Ok(())
 }
@@ -0,0 +1,44 @@
+//! RemoveSettlementMint instruction handler
+
+use {
+    crate::{error::TreasuryBondsError, state::configs::TreasuryBondsConfigs},
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct RemoveSettlementMint<'info> {
+    #[account(mut,
+        constraint = treasury_bonds_configs.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        constraint = treasury_bonds_configs.admin == owner.key() @ TreasuryBondsError::InvalidAdmin,
+        seeds = [b"treasury-bonds-configs"],
+        bump
+    )]
+    pub treasury_bonds_configs: Account<'info, TreasuryBondsConfigs>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RemoveSettlementMintParams {
+    pub settlement_mint: Pubkey, // mint to remove from the allow-list
+}
+
+// treasury bonds already registered keep settling in their recorded mint,
+// the mint is only no longer accepted for new registrations
+pub fn remove_settlement_mint(
+    ctx: Context<RemoveSettlementMint>,
+    params: &RemoveSettlementMintParams,
+) -> Result<()> {
+    let treasury_bonds_configs = &mut ctx.accounts.treasury_bonds_configs;
+
+    if !treasury_bonds_configs.is_settlement_mint(&params.settlement_mint) {
+        return Err(TreasuryBondsError::SettlementMintNotAllowed.into());
+    }
+
+    treasury_bonds_configs
+        .settlement_mints
+        .retain(|x| *x != params.settlement_mint);
+
+    Ok(())
+}
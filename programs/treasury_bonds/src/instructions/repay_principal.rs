@@ -0,0 +1,161 @@
+//! RepayPrincipal instruction handler
+
+use {
+    crate::{
+        error::TreasuryBondsError,
+        state::{
+            amortization_schedule::AmortizationSchedule, deposit_base::DepositBase,
+            holding::Holding, investor::Investor, treasury_bonds::TreasuryBonds,
+        },
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+    },
+};
+
+#[derive(Accounts)]
+pub struct RepayPrincipal<'info> {
+    #[account(has_one = deposit_account,
+        constraint = treasury_bonds.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [
+            b"treasury-bonds",
+            treasury_bonds.owner.as_ref(),
+            treasury_bonds.issue_no.as_bytes()
+        ],
+        bump = treasury_bonds.bump
+    )]
+    pub treasury_bonds: Account<'info, TreasuryBonds>,
+    #[account(mut, has_one = treasury_bonds,
+        constraint = amortization_schedule.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [b"amortization-schedule", treasury_bonds.key().as_ref()],
+        bump
+    )]
+    pub amortization_schedule: Box<Account<'info, AmortizationSchedule>>,
+    #[account(has_one = owner,
+        constraint = investor.active @ TreasuryBondsError::InvalidInvestorStatus
+    )]
+    pub investor: Account<'info, Investor>,
+    #[account(mut, has_one = investor, has_one = treasury_bonds,
+        seeds = [b"holding", investor.key().as_ref(), treasury_bonds.key().as_ref()],
+        bump = holding.bump
+    )]
+    pub holding: Account<'info, Holding>,
+    // paid out of the treasury vault
+    #[account(mut,
+        constraint = sender_tokens.key() == treasury_bonds.vault_tokens @ TreasuryBondsError::InvalidVaultAccount
+    )]
+    pub sender_tokens: Account<'info, TokenAccount>,
+    #[account(mut,
+        constraint = recipient_tokens.mint == mint_token.key() @ TreasuryBondsError::InvalidSettlementMint,
+        constraint = recipient_tokens.owner == owner.key() @ TreasuryBondsError::InvalidTokenAccountOwner
+    )]
+    pub recipient_tokens: Account<'info, TokenAccount>,
+    #[account(mut,
+        address = treasury_bonds.settlement_mint @ TreasuryBondsError::InvalidSettlementMint
+    )]
+    pub mint_token: Account<'info, Mint>,
+    #[account(mut,
+        constraint = deposit_account.is_initialized @ TreasuryBondsError::AccountNotInitialized
+    )]
+    pub deposit_account: Account<'info, DepositBase>,
+    #[account(seeds = [b"auth", deposit_account.key().as_ref()], bump)]
+    /// CHECK: no need to check this.
+    pub pda_auth: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"treasury-vault", pda_auth.key().as_ref()], bump)]
+    pub treasury_vault: SystemAccount<'info>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+// repays the principal tranches that have fallen due to the investor,
+// pro-rata to the treasury bonds held
+pub fn repay_principal(ctx: Context<RepayPrincipal>) -> Result<()> {
+    let treasury_bonds = &ctx.accounts.treasury_bonds;
+    let amortization_schedule = &mut ctx.accounts.amortization_schedule;
+    let holding = &mut ctx.accounts.holding;
+    let sender_tokens = &ctx.accounts.sender_tokens;
+    let recipient_tokens = &ctx.accounts.recipient_tokens;
+    let mint_token = &ctx.accounts.mint_token;
+    let pda_auth = &mut ctx.accounts.pda_auth;
+    let treasury_vault = &mut ctx.accounts.treasury_vault;
+    let token_program = &ctx.accounts.token_program;
+    let available_funds: u32 = holding.available_funds;
+    let tranches_repaid = holding.principal_tranches_repaid;
+    let decimals: u8 = treasury_bonds.decimals;
+
+    // investor's available funds should exceed zero
+    if available_funds == 0 {
+        return Err(TreasuryBondsError::InsufficientFunds.into());
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    let tranches_due = amortization_schedule.tranches_due(now);
+
+    // only tranches that have fallen due and are not yet repaid can be claimed
+    if tranches_due <= tranches_repaid {
+        return Err(TreasuryBondsError::NoPrincipalDue.into());
+    }
+
+    let face_value = treasury_bonds
+        .face_value_of(available_funds)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    // principal repaid = face value * share of the face value repaid by the tranches due
+    let repayment_rate = amortization_schedule.repayment_rate_of(tranches_repaid..tranches_due);
+    let _amount = AmortizationSchedule::share_of(face_value, repayment_rate)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    // the repaid principal no longer forms part of the cost of the treasury bonds held
+    let outstanding_rate = amortization_schedule.outstanding_rate(tranches_repaid);
+    let cost_basis = (holding.cost_basis as u128)
+        .checked_mul(repayment_rate as u128)
+        .and_then(|cost_basis| cost_basis.checked_div(outstanding_rate as u128))
+        .and_then(|cost_basis| u64::try_from(cost_basis).ok())
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    holding.cost_basis = holding
+        .cost_basis
+        .checked_sub(cost_basis)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    // record the repaid tranches so that no tranche can be claimed twice
+    holding.principal_tranches_repaid = tranches_due;
+
+    holding.total_principal_received = holding
+        .total_principal_received
+        .checked_add(_amount)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    amortization_schedule.total_principal_repaid = amortization_schedule
+        .total_principal_repaid
+        .checked_add(_amount)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    // Transfer principal from treasury vault to recipient
+    let cpi_accounts = TransferChecked {
+        from: sender_tokens.to_account_info(),
+        mint: mint_token.to_account_info(),
+        to: recipient_tokens.to_account_info(),
+        authority: treasury_vault.to_account_info(),
+    };
+
+    let seeds = &[
+        b"treasury-vault",
+        pda_auth.to_account_info().key.as_ref(),
+        &[ctx.bumps.treasury_vault],
+    ];
+
+    let signer = &[&seeds[..]];
+
+    let cpi = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer);
+
+    transfer_checked(cpi, _amount, decimals)?;
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+//! RevokeRole instruction handler
+
+use {
+    crate::{
+        error::TreasuryBondsError,
+        state::configs::{RoleGrant, TreasuryBondsConfigs},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct RevokeRole<'info> {
+    #[account(mut,
+        constraint = treasury_bonds_configs.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        constraint = treasury_bonds_configs.admin == owner.key() @ TreasuryBondsError::InvalidAdmin,
+        seeds = [b"treasury-bonds-configs"],
+        bump
+    )]
+    pub treasury_bonds_configs: Account<'info, TreasuryBondsConfigs>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RevokeRoleParams {
+    pub role: u8,        // role to revoke
+    pub account: Pubkey, // publickey to revoke the role from
+}
+
+// treasury bonds registered by a revoked issuance operator remain seeded by its key,
+// they are managed by the remaining issuance operators
+pub fn revoke_role(ctx: Context<RevokeRole>, params: &RevokeRoleParams) -> Result<()> {
+    let treasury_bonds_configs = &mut ctx.accounts.treasury_bonds_configs;
+
+    if !treasury_bonds_configs.has_role(params.role, &params.account) {
+        return Err(TreasuryBondsError::RoleNotGranted.into());
+    }
+
+    let role_grant = RoleGrant {
+        role: params.role,
+        account: params.account,
+    };
+
+    treasury_bonds_configs
+        .roles
+        .retain(|grant| *grant != role_grant);
+
+    Ok(())
+}
@@ -2,13 +2,24 @@
 
 use {
     crate::{
+        bond_units::{freeze_bond_tokens, thaw_bond_tokens},
         error::TreasuryBondsError,
-        state::{deposit_base::DepositBase, investor::Investor, treasury_bonds::TreasuryBonds},
+        events::TreasuryBondsSold,
+        state::{
+            amortization_schedule::AmortizationSchedule,
+            coupon_schedule::CouponSchedule,
+            holding::Holding,
+            investor::Investor,
+            offer::Offer,
+            treasury_bonds::{TreasuryBonds, PAR_CLEAN_PRICE, ZERO_COUPON_BOND},
+        },
     },
     anchor_lang::prelude::*,
     anchor_spl::{
         associated_token::AssociatedToken,
         token::{transfer, Mint, Token, TokenAccount, Transfer},
+        token_2022::spl_token_2022::onchain::invoke_transfer_checked,
+        token_interface::{self, TokenInterface},
     },
 };
 
@@ -17,38 +28,110 @@ use {
 pub struct SellTreasuryBonds<'info> {
     #[account(mut,
         constraint = treasury_bonds.is_initialized @ TreasuryBondsError::AccountNotInitialized,
-        constraint = !treasury_bonds.is_matured @ TreasuryBondsError::InvalidBondMaturityStatus
+        seeds = [
+            b"treasury-bonds",
+            treasury_bonds.owner.as_ref(),
+            treasury_bonds.issue_no.as_bytes()
+        ],
+        bump = treasury_bonds.bump
     )]
     pub treasury_bonds: Account<'info, TreasuryBonds>,
-    #[account(mut,
+    #[account(has_one = treasury_bonds,
+        constraint = coupon_schedule.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [b"coupon-schedule", treasury_bonds.key().as_ref()],
+        bump
+    )]
+    pub coupon_schedule: Account<'info, CouponSchedule>,
+    #[account(has_one = treasury_bonds,
+        constraint = amortization_schedule.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [b"amortization-schedule", treasury_bonds.key().as_ref()],
+        bump
+    )]
+    pub amortization_schedule: Box<Account<'info, AmortizationSchedule>>,
+    // seller's offer accepted by the buyer
+    #[account(mut, has_one = treasury_bonds, has_one = seller_investor,
+        seeds = [b"offer", treasury_bonds.key().as_ref(), seller_investor.key().as_ref()],
+        bump = offer.bump
+    )]
+    pub offer: Account<'info, Offer>,
+    #[account(
         constraint = seller_investor.active @ TreasuryBondsError::InvalidInvestorStatus
     )]
     pub seller_investor: Account<'info, Investor>,
-    #[account(mut,has_one = owner,
-        constraint = buyer_investor.active @ TreasuryBondsError::InvalidInvestorStatus
+    #[account(mut, has_one = treasury_bonds,
+        seeds = [b"holding", seller_investor.key().as_ref(), treasury_bonds.key().as_ref()],
+        bump = seller_holding.bump
+    )]
+    pub seller_holding: Account<'info, Holding>,
+    #[account(has_one = owner,
+        constraint = buyer_investor.active @ TreasuryBondsError::InvalidInvestorStatus,
+        constraint = buyer_investor.key() != seller_investor.key() @ TreasuryBondsError::SelfTrade
     )]
     pub buyer_investor: Account<'info, Investor>,
-    #[account(mut)]
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + Holding::INIT_SPACE,
+        seeds = [b"holding", buyer_investor.key().as_ref(), treasury_bonds.key().as_ref()],
+        bump
+    )]
+    pub buyer_holding: Account<'info, Holding>,
+    // buyer
+    #[account(mut,
+        constraint = from_account.mint == mint_token.key() @ TreasuryBondsError::InvalidSettlementMint,
+        constraint = from_account.owner == owner.key() @ TreasuryBondsError::InvalidTokenAccountOwner
+    )]
     pub from_account: Account<'info, TokenAccount>,
-    #[account(mut)]
+    // seller
+    #[account(mut,
+        constraint = to_account.mint == mint_token.key() @ TreasuryBondsError::InvalidSettlementMint,
+        constraint = to_account.owner == seller_investor.owner @ TreasuryBondsError::InvalidTokenAccountOwner
+    )]
     pub to_account: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub mint_token: Signer<'info>,
+    #[account(address = treasury_bonds.settlement_mint @ TreasuryBondsError::InvalidSettlementMint)]
+    pub mint_token: Account<'info, Mint>,
+    #[account(mut,
+        address = treasury_bonds.bond_mint @ TreasuryBondsError::InvalidBondMint
+    )]
+    pub bond_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    // bond units are transferred from the seller's token account to the buyer's by the offer (delegate)
+    #[account(mut,
+        associated_token::mint = bond_mint,
+        associated_token::authority = seller,
+        associated_token::token_program = bond_token_program
+    )]
+    pub seller_bond_tokens: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = bond_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = bond_token_program
+    )]
+    pub buyer_bond_tokens: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    // seller receives the offer's rent once the offer is filled
+    #[account(mut, address = offer.owner)]
+    pub seller: SystemAccount<'info>,
     // mut makes it changeble (mutable)
     #[account(mut)]
     pub owner: Signer<'info>,
     pub token_program: Program<'info, Token>,
-    pub associate_token_program: Program<'info, AssociatedToken>,
+    pub bond_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct SellTreasuryBondsParams {
-    pub amount: u32,
+    pub amount: u32,      // amount of treasury bonds bought
+    pub clean_price: u32, // agreed clean price per 100 face value i.e the offer's clean price
 }
 
-pub fn sell_treasury_bonds(
-    ctx: Context<SellTreasuryBonds>,
+// buyer's acceptance of a seller's offer i.e delivery versus payment, the buyer pays the
+// dirty price (agreed clean price plus coupon accrued since the last coupon date) to the
+// seller and the bond units are delivered to the buyer in the same transaction
+pub fn sell_treasury_bonds<'info>(
+    ctx: Context<'_, '_, '_, 'info, SellTreasuryBonds<'info>>,
     params: &SellTreasuryBondsParams,
 ) -> Result<()> {
     msg!("Validate inputs");
@@ -57,59 +140,183 @@ pub fn sell_treasury_bonds(
     }
 
     let treasury_bonds = &mut ctx.accounts.treasury_bonds;
-    let seller_investor = &mut ctx.accounts.seller_investor;
-    let buyer_investor = &mut ctx.accounts.buyer_investor;
+    let coupon_schedule = &ctx.accounts.coupon_schedule;
+    let amortization_schedule = &ctx.accounts.amortization_schedule;
+    let offer = &mut ctx.accounts.offer;
+    let buyer_investor = &ctx.accounts.buyer_investor;
+    let seller_holding = &mut ctx.accounts.seller_holding;
+    let buyer_holding = &mut ctx.accounts.buyer_holding;
     let unit_cost_of_treasury_bonds: u32 = treasury_bonds.unit_cost_of_treasury_bonds;
-    let total_units_treasury_bonds_seller: u32 = seller_investor.total_units_treasury_bonds;
-    let available_funds_seller: u32 = seller_investor.available_funds;
-    let total_units_treasury_bonds_buyer: u32 = buyer_investor.total_units_treasury_bonds;
-    let available_funds_buyer: u32 = buyer_investor.available_funds;
-    let decimals: u8 = treasury_bonds.decimals;
+    let total_units_treasury_bonds_seller: u32 = seller_holding.total_units_treasury_bonds;
+    let available_funds_seller: u32 = seller_holding.available_funds;
+    let total_units_treasury_bonds_buyer: u32 = buyer_holding.total_units_treasury_bonds;
+    let available_funds_buyer: u32 = buyer_holding.available_funds;
     let _amount = params.amount;
 
+    // treasury bonds should not have matured
+    let now = Clock::get()?.unix_timestamp;
+    if treasury_bonds.has_matured(now) {
+        return Err(TreasuryBondsError::InvalidBondMaturityStatus.into());
+    }
+
+    // offer should not have expired
+    if offer.has_expired(now) {
+        return Err(TreasuryBondsError::OfferExpired.into());
+    }
+
+    // buyer should agree to the seller's clean price
+    if params.clean_price != offer.clean_price {
+        return Err(TreasuryBondsError::InvalidOfferPrice.into());
+    }
+
+    // amount bought should not exceed the amount offered, and any amount left on offer
+    // should still meet the minimum trade amount
+    if offer.amount < _amount {
+        return Err(TreasuryBondsError::OfferAmountExceeded.into());
+    }
+
+    let remaining_offer_amount = offer.amount - _amount;
+    if remaining_offer_amount > 0 && remaining_offer_amount < treasury_bonds.minimum_trade_amount {
+        return Err(TreasuryBondsError::InvalidTradeAmount.into());
+    }
+
     // investor's(seller) available funds should exceed zero
     if available_funds_seller == 0 {
         return Err(TreasuryBondsError::InsufficientFunds.into());
     }
 
-    // investor's(seller) available funds should match transfer amount
-    if available_funds_seller == _amount {
-    } else {
-        return Err(TreasuryBondsError::MismatchedAmount.into());
+    // investor's(seller) available funds should cover the transfer amount
+    if available_funds_seller < _amount {
+        return Err(TreasuryBondsError::InsufficientFunds.into());
     }
 
+    // partial sells should meet the minimum trade amount
+    if !treasury_bonds.is_valid_trade_amount(_amount, available_funds_seller) {
+        return Err(TreasuryBondsError::InvalidTradeAmount.into());
+    }
+
+    // seller and buyer should have been paid the coupons and principal that have fallen due
+    let periods_due = coupon_schedule.periods_due(now);
+    let tranches_due = amortization_schedule.tranches_due(now);
+    seller_holding.verify_settled(periods_due, tranches_due)?;
+    buyer_holding.verify_settled(periods_due, tranches_due)?;
+
     // Get unit_cost_of_treasury_bonds from the product of unit_cost_of_treasury_bonds and actual_amount
     let unit_cost_of_treasury_bonds = unit_cost_of_treasury_bonds
         .checked_mul(_amount)
         .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
 
-    // Deduct sold unit_cost_of_treasury_bonds from seller_investor's total_units_treasury_bonds
-    seller_investor.total_units_treasury_bonds = total_units_treasury_bonds_seller
+    // Deduct sold treasury bonds' cost basis from seller_holding's cost_basis
+    let cost_basis_seller = seller_holding
+        .cost_basis_of(_amount)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+    seller_holding.cost_basis = seller_holding
+        .cost_basis
+        .checked_sub(cost_basis_seller)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    // Deduct sold unit_cost_of_treasury_bonds from seller_holding's total_units_treasury_bonds
+    seller_holding.total_units_treasury_bonds = total_units_treasury_bonds_seller
         .checked_sub(unit_cost_of_treasury_bonds)
         .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
 
-    // Deduct actual_amount(sold unit_cost_of_treasury_bonds) from seller_investor's available funds
-    seller_investor.available_funds = available_funds_seller
+    // Deduct actual_amount(sold unit_cost_of_treasury_bonds) from seller_holding's available funds
+    seller_holding.available_funds = available_funds_seller
         .checked_sub(_amount)
         .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
 
+    // holding is created on the buyer's first purchase of the treasury bonds
+    if !buyer_holding.is_initialized {
+        buyer_holding.investor = buyer_investor.key();
+        buyer_holding.treasury_bonds = treasury_bonds.key();
+        buyer_holding.is_initialized = true;
+        buyer_holding.bump = ctx.bumps.buyer_holding;
+    }
+
+    // A seller with no available funds left is removed from the holders count
+    if seller_holding.available_funds == 0 {
+        treasury_bonds.holders_count = treasury_bonds
+            .holders_count
+            .checked_sub(1)
+            .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+    }
+
+    // buyer only earns coupons and principal falling due after the purchase
+    buyer_holding.coupon_periods_paid = periods_due;
+    buyer_holding.principal_tranches_repaid = tranches_due;
+
+    // A new bondholder is added to the holders count
+    if available_funds_buyer == 0 {
+        treasury_bonds.holders_count = treasury_bonds
+            .holders_count
+            .checked_add(1)
+            .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+    }
+
     // Increment buyer's total_units_treasury_bonds with new unit_treasury_bonds
-    buyer_investor.total_units_treasury_bonds = total_units_treasury_bonds_buyer
+    buyer_holding.total_units_treasury_bonds = total_units_treasury_bonds_buyer
         .checked_add(unit_cost_of_treasury_bonds)
         .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
 
     // Increment buyer's available_funds with new _amount
-    buyer_investor.available_funds = available_funds_buyer
+    buyer_holding.available_funds = available_funds_buyer
         .checked_add(_amount)
         .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
 
-    let base: u32 = 10;
-    let exponent = treasury_bonds.decimals as u32;
-    // lets get the amount in decimal format
-    // 10 ** 9 * 3(base 10, 9 decimals, 3 amount), // 3 amount of token to transfer (in smallest unit i.e 9 decimals)
-    let result = (base).pow(exponent);
-    let _amount = (_amount as u64)
-        .checked_mul(result as u64)
+    offer.amount = remaining_offer_amount;
+
+    let face_value = treasury_bonds
+        .face_value_of(_amount)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    // amortizing bonds trade on the face value still outstanding
+    let face_value =
+        AmortizationSchedule::share_of(face_value, amortization_schedule.outstanding_rate_at(now))
+            .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    // buyer pays the seller's clean price
+    let clean_amount = AmortizationSchedule::share_of(
+        treasury_bonds
+            .clean_value_of(_amount, offer.clean_price)
+            .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?,
+        amortization_schedule.outstanding_rate_at(now),
+    )
+    .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    // and compensates the seller for the coupon accrued since the last coupon date,
+    // which the buyer receives in full at the next coupon date
+    let accrued_coupon = if treasury_bonds.type_of_bond == ZERO_COUPON_BOND {
+        0
+    } else {
+        coupon_schedule
+            .accrued_coupon(
+                face_value,
+                treasury_bonds.coupon_rate,
+                treasury_bonds.day_count,
+                now,
+            )
+            .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?
+    };
+
+    // dirty price = clean price + accrued coupon (per 100 face value)
+    let accrued_price = (accrued_coupon as u128)
+        .checked_mul(PAR_CLEAN_PRICE as u128)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?
+        .checked_div(face_value as u128)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+    let dirty_price = u32::try_from(accrued_price)
+        .ok()
+        .and_then(|accrued_price| offer.clean_price.checked_add(accrued_price))
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    let _amount = clean_amount
+        .checked_add(accrued_coupon)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    // Increment buyer's cost_basis with the clean price paid
+    buyer_holding.cost_basis = buyer_holding
+        .cost_basis
+        .checked_add(clean_amount)
         .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
 
     transfer(
@@ -124,5 +331,74 @@ pub fn sell_treasury_bonds(
         _amount,
     )?;
 
+    let treasury_bonds_key = treasury_bonds.key();
+    let seller_investor_key = ctx.accounts.seller_investor.key();
+    let seeds = &[
+        b"offer",
+        treasury_bonds_key.as_ref(),
+        seller_investor_key.as_ref(),
+        &[offer.bump],
+    ];
+
+    let signer = &[&seeds[..]];
+
+    // Thaw the seller's and buyer's bond units for the program to move them
+    thaw_bond_tokens(
+        treasury_bonds,
+        &ctx.accounts.bond_mint,
+        &ctx.accounts.seller_bond_tokens,
+        &ctx.accounts.bond_token_program,
+    )?;
+    thaw_bond_tokens(
+        treasury_bonds,
+        &ctx.accounts.bond_mint,
+        &ctx.accounts.buyer_bond_tokens,
+        &ctx.accounts.bond_token_program,
+    )?;
+
+    // Transfer bond units from seller to buyer, against the payment above
+    // remaining accounts carry the transfer hook accounts of bond mints in Token-2022 mode
+    invoke_transfer_checked(
+        ctx.accounts.bond_token_program.key,
+        ctx.accounts.seller_bond_tokens.to_account_info(),
+        ctx.accounts.bond_mint.to_account_info(),
+        ctx.accounts.buyer_bond_tokens.to_account_info(),
+        offer.to_account_info(),
+        ctx.remaining_accounts,
+        unit_cost_of_treasury_bonds as u64,
+        ctx.accounts.bond_mint.decimals,
+        signer,
+    )?;
+
+    // Freeze the seller's and buyer's bond units again
+    freeze_bond_tokens(
+        treasury_bonds,
+        &ctx.accounts.bond_mint,
+        &ctx.accounts.seller_bond_tokens,
+        &ctx.accounts.bond_token_program,
+    )?;
+    freeze_bond_tokens(
+        treasury_bonds,
+        &ctx.accounts.bond_mint,
+        &ctx.accounts.buyer_bond_tokens,
+        &ctx.accounts.bond_token_program,
+    )?;
+
+    emit!(TreasuryBondsSold {
+        treasury_bonds: treasury_bonds_key,
+        seller_investor: seller_investor_key,
+        buyer_investor: ctx.accounts.buyer_investor.key(),
+        amount: params.amount,
+        clean_price: offer.clean_price,
+        dirty_price,
+        accrued_coupon,
+        settlement_amount: _amount,
+    });
+
+    // offer is closed once filled, returning its rent to the seller
+    if offer.amount == 0 {
+        offer.close(ctx.accounts.seller.to_account_info())?;
+    }
+
     Ok(())
 }
@@ -0,0 +1,108 @@
+//! SetApprovers instruction handler
+
+use {
+    crate::{
+        error::TreasuryBondsError,
+        state::{
+            configs::TreasuryBondsConfigs,
+            proposal::{Proposal, MAX_APPROVERS, MIN_APPROVAL_THRESHOLD, SET_APPROVERS},
+        },
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct SetApprovers<'info> {
+    #[account(mut,
+        constraint = treasury_bonds_configs.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        constraint = treasury_bonds_configs.admin == owner.key() @ TreasuryBondsError::InvalidAdmin,
+        seeds = [b"treasury-bonds-configs"],
+        bump
+    )]
+    pub treasury_bonds_configs: Account<'info, TreasuryBondsConfigs>,
+    // changes to the approvers should be approved by the approvers in place,
+    // not needed when the first approvers are set
+    #[account(mut,
+        constraint = !proposal.is_executed @ TreasuryBondsError::ProposalAlreadyExecuted,
+        constraint = proposal.is_approved(&treasury_bonds_configs) @ TreasuryBondsError::ProposalNotApproved,
+        seeds = [b"proposal", proposal.proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Option<Box<Account<'info, Proposal>>>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetApproversParams {
+    pub approvers: Vec<Pubkey>, // approvers of proposals for sensitive admin actions
+    pub approval_threshold: u8, // approvals required to execute a proposal i.e M of N approvers
+    pub proposal_timeout: i64,  // seconds a proposal remains open for approval and execution
+}
+
+// sets the M-of-N approvers of proposals. the configs admin sets the first approvers,
+// later changes are executed through a proposal approved by the approvers in place.
+// pending proposals are counted against the approvers and threshold in place when they are executed
+pub fn set_approvers(ctx: Context<SetApprovers>, params: &SetApproversParams) -> Result<()> {
+    let treasury_bonds_configs = &mut ctx.accounts.treasury_bonds_configs;
+
+    msg!("Validate inputs");
+    if params.approvers.is_empty() || params.approvers.len() > MAX_APPROVERS {
+        return Err(TreasuryBondsError::InvalidApprovers.into());
+    }
+
+    // an approver is listed once
+    let has_duplicates = params
+        .approvers
+        .iter()
+        .enumerate()
+        .any(|(i, approver)| params.approvers[..i].contains(approver));
+
+    if has_duplicates {
+        return Err(TreasuryBondsError::InvalidApprovers.into());
+    }
+
+    // no single approver should be able to execute a proposal
+    if params.approval_threshold >= MIN_APPROVAL_THRESHOLD
+        && params.approval_threshold as usize <= params.approvers.len()
+    {
+    } else {
+        return Err(TreasuryBondsError::InvalidApprovers.into());
+    }
+
+    if params.proposal_timeout <= 0 {
+        return Err(TreasuryBondsError::InvalidApprovers.into());
+    }
+
+    // action should be authorized by an approved proposal once approvers are set
+    if !treasury_bonds_configs.approvers.is_empty() {
+        let proposal = ctx
+            .accounts
+            .proposal
+            .as_mut()
+            .ok_or(TreasuryBondsError::ProposalNotApproved)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        if proposal.has_expired(now) {
+            return Err(TreasuryBondsError::ProposalExpired.into());
+        }
+
+        if proposal.authorizes(
+            SET_APPROVERS,
+            &treasury_bonds_configs.key(),
+            &params.try_to_vec()?,
+        ) {
+        } else {
+            return Err(TreasuryBondsError::ProposalMismatch.into());
+        }
+
+        proposal.is_executed = true;
+    }
+
+    treasury_bonds_configs.approvers = params.approvers.to_vec();
+    treasury_bonds_configs.approval_threshold = params.approval_threshold;
+    treasury_bonds_configs.proposal_timeout = params.proposal_timeout;
+
+    Ok(())
+}
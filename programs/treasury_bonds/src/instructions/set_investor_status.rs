@@ -0,0 +1,48 @@
+//! SetInvestorStatus instruction handler
+
+use {
+    crate::{
+        error::TreasuryBondsError,
+        state::{
+            configs::{TreasuryBondsConfigs, COMPLIANCE_OFFICER},
+            investor::Investor,
+        },
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct SetInvestorStatus<'info> {
+    #[account(
+        constraint = treasury_bonds_configs.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        constraint = treasury_bonds_configs.has_role(COMPLIANCE_OFFICER, &owner.key()) @ TreasuryBondsError::MissingRole,
+        seeds = [b"treasury-bonds-configs"],
+        bump
+    )]
+    pub treasury_bonds_configs: Account<'info, TreasuryBondsConfigs>,
+    #[account(mut,
+        seeds = [b"investor", investor.owner.as_ref()],
+        bump
+    )]
+    pub investor: Account<'info, Investor>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetInvestorStatusParams {
+    pub active: bool, // inactive investors can not buy, sell, redeem or receive payouts
+}
+
+// compliance officer activates or deactivates an investor i.e on KYC review
+pub fn set_investor_status(
+    ctx: Context<SetInvestorStatus>,
+    params: &SetInvestorStatusParams,
+) -> Result<()> {
+    let investor = &mut ctx.accounts.investor;
+
+    investor.active = params.active;
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+//! SetTaxProfile instruction handler
+
+use {
+    crate::{
+        error::TreasuryBondsError,
+        state::{
+            configs::{TreasuryBondsConfigs, ISSUANCE_OPERATOR},
+            tax_profile::TaxProfile,
+            treasury_bonds::{TreasuryBonds, ZERO_COUPON_BOND},
+        },
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::TokenAccount,
+};
+
+#[derive(Accounts)]
+pub struct SetTaxProfile<'info> {
+    #[account(
+        constraint = treasury_bonds_configs.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        constraint = treasury_bonds_configs.has_role(ISSUANCE_OPERATOR, &owner.key()) @ TreasuryBondsError::MissingRole,
+        seeds = [b"treasury-bonds-configs"],
+        bump
+    )]
+    pub treasury_bonds_configs: Box<Account<'info, TreasuryBondsConfigs>>,
+    #[account(
+        constraint = treasury_bonds.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        constraint = treasury_bonds.type_of_bond != ZERO_COUPON_BOND @ TreasuryBondsError::CouponNotApplicable,
+        seeds = [
+            b"treasury-bonds",
+            treasury_bonds.owner.as_ref(),
+            treasury_bonds.issue_no.as_bytes()
+        ],
+        bump = treasury_bonds.bump
+    )]
+    pub treasury_bonds: Account<'info, TreasuryBonds>,
+    // init means to create account
+    // bump to use unique address for account
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + TaxProfile::INIT_SPACE,
+        seeds = [b"tax-profile", treasury_bonds.key().as_ref()],
+        bump
+    )]
+    pub tax_profile: Account<'info, TaxProfile>,
+    // withheld tax is paid into the tax authority's token account
+    #[account(
+        constraint = tax_authority_tokens.mint == treasury_bonds.settlement_mint @ TreasuryBondsError::InvalidSettlementMint
+    )]
+    pub tax_authority_tokens: Account<'info, TokenAccount>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetTaxProfileParams {
+    pub withholding_tax_rate: Option<u16>, // withholding tax on coupons (basis points), defaults by tenor
+}
+
+// sets the tax profile applied when paying coupons of the treasury bonds.
+// infrastructure bonds are exempt from withholding tax. the tax profile can only
+// be changed afterwards through an approved proposal i.e update_tax_profile
+pub fn set_tax_profile(ctx: Context<SetTaxProfile>, params: &SetTaxProfileParams) -> Result<()> {
+    let treasury_bonds = &ctx.accounts.treasury_bonds;
+    let tax_profile = &mut ctx.accounts.tax_profile;
+    let tax_authority_tokens = &ctx.accounts.tax_authority_tokens;
+
+    msg!("Validate inputs");
+    tax_profile.apply(
+        treasury_bonds,
+        params.withholding_tax_rate,
+        tax_authority_tokens.key(),
+    )?;
+
+    // tax_profile
+    tax_profile.treasury_bonds = treasury_bonds.key();
+    tax_profile.is_initialized = true;
+    tax_profile.bump = ctx.bumps.tax_profile;
+
+    Ok(())
+}
@@ -0,0 +1,307 @@
+//! SettleBid instruction handler
+
+use {
+    crate::{
+        bond_units::{freeze_bond_tokens, thaw_bond_tokens},
+        error::TreasuryBondsError,
+        state::{
+            amortization_schedule::AmortizationSchedule, auction::Auction, bid::Bid,
+            coupon_schedule::CouponSchedule, holding::Holding, investor::Investor,
+            treasury_bonds::TreasuryBonds,
+        },
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token::{transfer, Mint, Token, TokenAccount, Transfer},
+        token_interface::{self, TokenInterface},
+    },
+};
+
+#[derive(Accounts)]
+pub struct SettleBid<'info> {
+    #[account(mut,
+        constraint = treasury_bonds.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [
+            b"treasury-bonds",
+            treasury_bonds.owner.as_ref(),
+            treasury_bonds.issue_no.as_bytes()
+        ],
+        bump = treasury_bonds.bump
+    )]
+    pub treasury_bonds: Account<'info, TreasuryBonds>,
+    #[account(has_one = treasury_bonds,
+        constraint = coupon_schedule.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [b"coupon-schedule", treasury_bonds.key().as_ref()],
+        bump
+    )]
+    pub coupon_schedule: Account<'info, CouponSchedule>,
+    #[account(has_one = treasury_bonds,
+        constraint = amortization_schedule.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [b"amortization-schedule", treasury_bonds.key().as_ref()],
+        bump
+    )]
+    pub amortization_schedule: Box<Account<'info, AmortizationSchedule>>,
+    #[account(mut, has_one = treasury_bonds,
+        constraint = auction.is_closed @ TreasuryBondsError::AuctionNotClosed,
+        seeds = [b"auction", treasury_bonds.key().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+    // bid is closed once settled, returning its rent to the investor
+    #[account(mut, close = owner, has_one = auction, has_one = investor, has_one = owner,
+        seeds = [b"bid", auction.key().as_ref(), investor.key().as_ref()],
+        bump = bid.bump
+    )]
+    pub bid: Account<'info, Bid>,
+    // deactivated investors can still settle their bids to be refunded escrow not allotted to them
+    #[account(has_one = owner)]
+    pub investor: Account<'info, Investor>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + Holding::INIT_SPACE,
+        seeds = [b"holding", investor.key().as_ref(), treasury_bonds.key().as_ref()],
+        bump
+    )]
+    pub holding: Account<'info, Holding>,
+    #[account(mut,
+        associated_token::mint = mint_token,
+        associated_token::authority = auction
+    )]
+    pub escrow_tokens: Account<'info, TokenAccount>,
+    // purchase proceeds are paid into the treasury vault
+    #[account(mut,
+        constraint = recipient_tokens.key() == treasury_bonds.vault_tokens @ TreasuryBondsError::InvalidVaultAccount
+    )]
+    pub recipient_tokens: Account<'info, TokenAccount>,
+    // investor is refunded the rest of the escrowed amount, or pays any amount above it
+    #[account(mut,
+        constraint = refund_tokens.mint == mint_token.key() @ TreasuryBondsError::InvalidSettlementMint,
+        constraint = refund_tokens.owner == owner.key() @ TreasuryBondsError::InvalidTokenAccountOwner
+    )]
+    pub refund_tokens: Account<'info, TokenAccount>,
+    #[account(address = treasury_bonds.settlement_mint @ TreasuryBondsError::InvalidSettlementMint)]
+    pub mint_token: Account<'info, Mint>,
+    #[account(mut,
+        address = treasury_bonds.bond_mint @ TreasuryBondsError::InvalidBondMint
+    )]
+    pub bond_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    // bond units are minted to the investor's token account
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = bond_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = bond_token_program
+    )]
+    pub bond_tokens: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub bond_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+// allots the bid's share of the auction to the investor's holding at the clearing price,
+// paying for it out of escrow and refunding the rest of the escrowed amount to the investor.
+// bids that were not allotted can be settled after the investor is deactivated, so that their
+// escrow is not locked in the auction
+pub fn settle_bid(ctx: Context<SettleBid>) -> Result<()> {
+    let treasury_bonds = &mut ctx.accounts.treasury_bonds;
+    let coupon_schedule = &ctx.accounts.coupon_schedule;
+    let amortization_schedule = &ctx.accounts.amortization_schedule;
+    let auction = &mut ctx.accounts.auction;
+    let bid = &ctx.accounts.bid;
+    let investor = &ctx.accounts.investor;
+    let holding = &mut ctx.accounts.holding;
+    let escrow_tokens = &ctx.accounts.escrow_tokens;
+    let recipient_tokens = &ctx.accounts.recipient_tokens;
+    let refund_tokens = &ctx.accounts.refund_tokens;
+    let token_program = &ctx.accounts.token_program;
+    let bond_mint = &ctx.accounts.bond_mint;
+    let bond_tokens = &ctx.accounts.bond_tokens;
+    let bond_token_program = &ctx.accounts.bond_token_program;
+    let unit_cost_of_treasury_bonds: u32 = treasury_bonds.unit_cost_of_treasury_bonds;
+    let total_available_funds = treasury_bonds.total_available_funds;
+    let total_units_treasury_bonds: u32 = holding.total_units_treasury_bonds;
+    let available_funds: u32 = holding.available_funds;
+
+    let now = Clock::get()?.unix_timestamp;
+    let allotted_amount = auction
+        .allot_bid(bid)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    // only active investors can be allotted treasury bonds
+    if allotted_amount > 0 && !investor.active {
+        return Err(TreasuryBondsError::InvalidInvestorStatus.into());
+    }
+
+    // holding is created on the investor's first purchase of the treasury bonds
+    if !holding.is_initialized {
+        holding.investor = investor.key();
+        holding.treasury_bonds = treasury_bonds.key();
+        holding.is_initialized = true;
+        holding.bump = ctx.bumps.holding;
+    }
+
+    // allotments are priced at the clearing price i.e the cut-off yield, or at the purchase
+    // price (zero coupon bonds are bought at a discount from face value) when no competitive
+    // bid is accepted
+    let purchase_amount = match auction.clearing_price {
+        Some(clearing_price) => treasury_bonds
+            .clean_value_of(allotted_amount, clearing_price)
+            .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?,
+        None => {
+            let face_value = treasury_bonds
+                .face_value_of(allotted_amount)
+                .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+            treasury_bonds
+                .purchase_price(face_value, now)
+                .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?
+        }
+    };
+
+    // non-competitive bids escrow the purchase price, which can fall short of the clearing price
+    let escrow_payment_amount = purchase_amount.min(bid.escrowed_amount);
+    let top_up_amount = purchase_amount
+        .checked_sub(escrow_payment_amount)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+    let refund_amount = bid
+        .escrowed_amount
+        .checked_sub(escrow_payment_amount)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    // Get unit_treasury_bonds from the product of unit_cost_of_treasury_bonds and allotted_amount
+    let unit_treasury_bonds = unit_cost_of_treasury_bonds
+        .checked_mul(allotted_amount)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    if allotted_amount > 0 {
+        // bondholders only earn coupons and principal falling due after the purchase
+        let periods_due = coupon_schedule.periods_due(now);
+        let tranches_due = amortization_schedule.tranches_due(now);
+        holding.verify_settled(periods_due, tranches_due)?;
+        holding.coupon_periods_paid = periods_due;
+        holding.principal_tranches_repaid = tranches_due;
+
+        // A new bondholder is added to the holders count
+        if available_funds == 0 {
+            treasury_bonds.holders_count = treasury_bonds
+                .holders_count
+                .checked_add(1)
+                .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+        }
+
+        // Increment total_units_treasury_bonds with new unit_treasury_bonds
+        holding.total_units_treasury_bonds = total_units_treasury_bonds
+            .checked_add(unit_treasury_bonds)
+            .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+        // Increment available_funds with allotted_amount
+        holding.available_funds = available_funds
+            .checked_add(allotted_amount)
+            .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+        // Increment cost_basis with the price paid
+        holding.cost_basis = holding
+            .cost_basis
+            .checked_add(purchase_amount)
+            .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+        // Increment total_available_funds with allotted_amount
+        // (total_amounts_accepted was incremented by the auction's allotment when it was closed)
+        treasury_bonds.total_available_funds =
+            total_available_funds
+                .checked_add(allotted_amount)
+                .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+    }
+
+    let treasury_bonds_key = treasury_bonds.key();
+    let seeds = &[b"auction", treasury_bonds_key.as_ref(), &[auction.bump]];
+
+    let signer = &[&seeds[..]];
+
+    // Transfer allotted amount from escrow to treasury
+    if escrow_payment_amount > 0 {
+        transfer(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                Transfer {
+                    from: escrow_tokens.to_account_info(),
+                    to: recipient_tokens.to_account_info(),
+                    authority: auction.to_account_info(),
+                },
+                signer,
+            ),
+            escrow_payment_amount,
+        )?;
+    }
+
+    // Transfer the amount above the escrowed amount from the investor to treasury
+    if top_up_amount > 0 {
+        transfer(
+            CpiContext::new(
+                token_program.to_account_info(),
+                Transfer {
+                    from: refund_tokens.to_account_info(),
+                    to: recipient_tokens.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            top_up_amount,
+        )?;
+    }
+
+    // Refund the rest of the escrowed amount to the investor
+    if refund_amount > 0 {
+        transfer(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                Transfer {
+                    from: escrow_tokens.to_account_info(),
+                    to: refund_tokens.to_account_info(),
+                    authority: auction.to_account_info(),
+                },
+                signer,
+            ),
+            refund_amount,
+        )?;
+    }
+
+    let seeds = &[
+        b"treasury-bonds",
+        treasury_bonds.owner.as_ref(),
+        treasury_bonds.issue_no.as_bytes(),
+        &[treasury_bonds.bump],
+    ];
+
+    let signer = &[&seeds[..]];
+
+    // Thaw the investor's bond units for the program to mint to them
+    thaw_bond_tokens(treasury_bonds, bond_mint, bond_tokens, bond_token_program)?;
+
+    // Mint allotted bond units to the investor
+    if unit_treasury_bonds > 0 {
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                bond_token_program.to_account_info(),
+                token_interface::MintTo {
+                    mint: bond_mint.to_account_info(),
+                    to: bond_tokens.to_account_info(),
+                    authority: treasury_bonds.to_account_info(),
+                },
+                signer,
+            ),
+            unit_treasury_bonds as u64,
+        )?;
+    }
+
+    // Freeze the investor's bond units again
+    freeze_bond_tokens(treasury_bonds, bond_mint, bond_tokens, bond_token_program)?;
+
+    Ok(())
+}
@@ -0,0 +1,190 @@
+//! SubmitBid instruction handler
+
+use {
+    crate::{
+        error::TreasuryBondsError,
+        state::{
+            amortization_schedule::AmortizationSchedule,
+            auction::{issue_price, Auction, MAX_YIELD_LEVELS},
+            bid::Bid,
+            coupon_schedule::CouponSchedule,
+            investor::Investor,
+            treasury_bonds::TreasuryBonds,
+        },
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer},
+};
+
+#[derive(Accounts)]
+#[instruction(params: SubmitBidParams)]
+pub struct SubmitBid<'info> {
+    #[account(
+        constraint = treasury_bonds.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [
+            b"treasury-bonds",
+            treasury_bonds.owner.as_ref(),
+            treasury_bonds.issue_no.as_bytes()
+        ],
+        bump = treasury_bonds.bump
+    )]
+    pub treasury_bonds: Account<'info, TreasuryBonds>,
+    #[account(has_one = treasury_bonds,
+        constraint = coupon_schedule.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [b"coupon-schedule", treasury_bonds.key().as_ref()],
+        bump
+    )]
+    pub coupon_schedule: Account<'info, CouponSchedule>,
+    #[account(has_one = treasury_bonds,
+        constraint = amortization_schedule.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [b"amortization-schedule", treasury_bonds.key().as_ref()],
+        bump
+    )]
+    pub amortization_schedule: Box<Account<'info, AmortizationSchedule>>,
+    #[account(mut, has_one = treasury_bonds,
+        constraint = auction.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [b"auction", treasury_bonds.key().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+    // init means to create account
+    // bump to use unique address for account
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Bid::INIT_SPACE,
+        seeds = [b"bid", auction.key().as_ref(), investor.key().as_ref()],
+        bump
+    )]
+    pub bid: Account<'info, Bid>,
+    #[account(has_one = owner,
+        constraint = investor.active @ TreasuryBondsError::InvalidInvestorStatus
+    )]
+    pub investor: Account<'info, Investor>,
+    #[account(mut,
+        constraint = sender_tokens.mint == mint_token.key() @ TreasuryBondsError::InvalidSettlementMint,
+        constraint = sender_tokens.owner == owner.key() @ TreasuryBondsError::InvalidTokenAccountOwner
+    )]
+    pub sender_tokens: Account<'info, TokenAccount>,
+    #[account(mut,
+        associated_token::mint = mint_token,
+        associated_token::authority = auction
+    )]
+    pub escrow_tokens: Account<'info, TokenAccount>,
+    #[account(address = treasury_bonds.settlement_mint @ TreasuryBondsError::InvalidSettlementMint)]
+    pub mint_token: Account<'info, Mint>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SubmitBidParams {
+    pub amount: u32,             // amount of treasury bonds bid for
+    pub yield_rate: Option<u16>, // yield bid (basis points) for competitive bids, none for non-competitive bids
+}
+
+pub fn submit_bid(ctx: Context<SubmitBid>, params: &SubmitBidParams) -> Result<()> {
+    msg!("Validate inputs");
+    if params.amount == 0 {
+        return Err(TreasuryBondsError::InvalidAmount.into());
+    }
+
+    if params.yield_rate == Some(0) {
+        return Err(TreasuryBondsError::InvalidBidYield.into());
+    }
+
+    let treasury_bonds = &ctx.accounts.treasury_bonds;
+    let coupon_schedule = &ctx.accounts.coupon_schedule;
+    let amortization_schedule = &ctx.accounts.amortization_schedule;
+    let auction = &mut ctx.accounts.auction;
+    let bid = &mut ctx.accounts.bid;
+    let investor = &ctx.accounts.investor;
+    let sender = &ctx.accounts.owner;
+    let sender_tokens = &ctx.accounts.sender_tokens;
+    let escrow_tokens = &ctx.accounts.escrow_tokens;
+    let token_program = &ctx.accounts.token_program;
+    let minimum_bid_amount = treasury_bonds.minimum_bid_amount;
+    let _amount = params.amount;
+
+    // bids are only accepted within the auction's bid window
+    let now = Clock::get()?.unix_timestamp;
+    if !auction.is_bid_window_open(now) {
+        return Err(TreasuryBondsError::AuctionNotOpen.into());
+    }
+
+    if _amount < minimum_bid_amount {
+        return Err(TreasuryBondsError::InvalidMinimumBidAmount.into());
+    }
+
+    // a new yield level takes up at least its share of the amount offered
+    let minimum_level_amount = treasury_bonds.total_amounts_offered / MAX_YIELD_LEVELS as u32;
+
+    match params.yield_rate {
+        Some(yield_rate) => {
+            auction.add_competitive_bid(yield_rate, _amount, minimum_level_amount)?
+        }
+        None => {
+            auction.total_non_competitive_bids = auction
+                .total_non_competitive_bids
+                .checked_add(_amount)
+                .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?
+        }
+    }
+
+    // competitive bids escrow the price at the yield bid, which covers the price at the
+    // cut-off yield they are allotted at. non-competitive bids escrow the purchase price
+    // (zero coupon bonds are bought at a discount from face value) and settle any difference
+    // from the price at the cut-off yield once allotted
+    let _amount = match params.yield_rate {
+        Some(yield_rate) => {
+            let clean_price = issue_price(
+                treasury_bonds,
+                coupon_schedule,
+                amortization_schedule,
+                yield_rate,
+            )
+            .ok_or(TreasuryBondsError::PriceYieldNotSolved)?;
+
+            treasury_bonds
+                .clean_value_of(_amount, clean_price)
+                .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?
+        }
+        None => {
+            let _amount = treasury_bonds
+                .face_value_of(_amount)
+                .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+            treasury_bonds
+                .purchase_price(_amount, now)
+                .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?
+        }
+    };
+
+    // bid
+    bid.auction = auction.key();
+    bid.investor = investor.key();
+    bid.owner = *sender.key;
+    bid.is_competitive = params.yield_rate.is_some();
+    bid.yield_rate = params.yield_rate.unwrap_or_default();
+    bid.amount = params.amount;
+    bid.escrowed_amount = _amount;
+    bid.bump = ctx.bumps.bid;
+
+    // Transfer bid amount to escrow until the auction is closed
+    transfer(
+        CpiContext::new(
+            token_program.to_account_info(),
+            Transfer {
+                from: sender_tokens.to_account_info(),
+                to: escrow_tokens.to_account_info(),
+                authority: sender.to_account_info(),
+            },
+        ),
+        _amount,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+//! TransferAdmin instruction handler
+
+use {
+    crate::{error::TreasuryBondsError, state::configs::TreasuryBondsConfigs},
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct TransferAdmin<'info> {
+    #[account(mut,
+        constraint = treasury_bonds_configs.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        constraint = treasury_bonds_configs.admin == owner.key() @ TreasuryBondsError::InvalidAdmin,
+        seeds = [b"treasury-bonds-configs"],
+        bump
+    )]
+    pub treasury_bonds_configs: Account<'info, TreasuryBondsConfigs>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct TransferAdminParams {
+    pub new_admin: Option<Pubkey>, // proposed configs admin, none cancels a pending transfer
+}
+
+// first step of the admin handover, the proposed admin takes over once it accepts
+pub fn transfer_admin(ctx: Context<TransferAdmin>, params: &TransferAdminParams) -> Result<()> {
+    let treasury_bonds_configs = &mut ctx.accounts.treasury_bonds_configs;
+
+    treasury_bonds_configs.pending_admin = params.new_admin;
+
+    Ok(())
+}
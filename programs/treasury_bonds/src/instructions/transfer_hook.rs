@@ -0,0 +1,137 @@
+//! TransferHook instruction handler
+
+use {
+    crate::{
+        error::TreasuryBondsError,
+        state::{investor::Investor, offer::Offer},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token_interface::{Mint, TokenAccount},
+    spl_tlv_account_resolution::{account::ExtraAccountMeta, seeds::Seed},
+};
+
+// index of the source and destination token accounts in the transfer hook execute instruction
+const SOURCE_TOKENS_INDEX: u8 = 0;
+const DESTINATION_TOKENS_INDEX: u8 = 2;
+// offset and length of the owner in the token account data
+const TOKEN_ACCOUNT_OWNER_OFFSET: u8 = 32;
+const TOKEN_ACCOUNT_OWNER_LENGTH: u8 = 32;
+
+#[derive(Accounts)]
+pub struct TransferHook<'info> {
+    #[account(token::mint = mint)]
+    pub source_tokens: InterfaceAccount<'info, TokenAccount>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(token::mint = mint)]
+    pub destination_tokens: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: owner or delegate of the source token account, validated by the token program.
+    pub authority: UncheckedAccount<'info>,
+    #[account(seeds = [b"extra-account-metas", mint.key().as_ref()], bump)]
+    /// CHECK: extra account metas of the bond mint, resolved by the token program.
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+    #[account(seeds = [b"investor", source_tokens.owner.as_ref()], bump)]
+    /// CHECK: investor account of the source owner, validated in the instruction handler.
+    pub source_investor: UncheckedAccount<'info>,
+    #[account(seeds = [b"investor", destination_tokens.owner.as_ref()], bump)]
+    /// CHECK: investor account of the destination owner, validated in the instruction handler.
+    pub destination_investor: UncheckedAccount<'info>,
+}
+
+// investor accounts of the source and destination token account owners,
+// appended by the token program to every transfer of the bond units
+pub fn extra_account_metas() -> Result<Vec<ExtraAccountMeta>> {
+    let investor_of = |token_account_index: u8| {
+        ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal {
+                    bytes: b"investor".to_vec(),
+                },
+                Seed::AccountData {
+                    account_index: token_account_index,
+                    data_index: TOKEN_ACCOUNT_OWNER_OFFSET,
+                    length: TOKEN_ACCOUNT_OWNER_LENGTH,
+                },
+            ],
+            false,
+            false,
+        )
+    };
+
+    Ok(vec![
+        investor_of(SOURCE_TOKENS_INDEX)?,
+        investor_of(DESTINATION_TOKENS_INDEX)?,
+    ])
+}
+
+// invoked by the token program on transfers of bond units minted in Token-2022 mode.
+// bond units only change hands through the program, so that the investors' holdings stay in
+// step with their token balances i.e delivered by an offer or the order book of the treasury
+// bonds, or paid into the order book's escrow. they can only be held by registered investors,
+// or escrowed by the order book, so both the source and destination owners are checked.
+pub fn transfer_hook(ctx: Context<TransferHook>, _amount: u64) -> Result<()> {
+    let mint = &ctx.accounts.mint;
+    let authority = &ctx.accounts.authority;
+
+    // the treasury bonds are the mint authority of their bond units
+    let treasury_bonds =
+        Option::<Pubkey>::from(mint.mint_authority).ok_or(TreasuryBondsError::InvalidBondMint)?;
+    let (order_book, _) =
+        Pubkey::find_program_address(&[b"order-book", treasury_bonds.as_ref()], &crate::ID);
+
+    if authority.key() == order_book
+        || ctx.accounts.destination_tokens.owner == order_book
+        || is_offer_of(authority, &treasury_bonds)
+    {
+    } else {
+        return Err(TreasuryBondsError::TransferNotAllowed.into());
+    }
+
+    if is_permitted_holder(
+        &ctx.accounts.source_tokens.owner,
+        &ctx.accounts.source_investor,
+        &order_book,
+    ) && is_permitted_holder(
+        &ctx.accounts.destination_tokens.owner,
+        &ctx.accounts.destination_investor,
+        &order_book,
+    ) {
+    } else {
+        return Err(TreasuryBondsError::InvalidInvestorStatus.into());
+    }
+
+    Ok(())
+}
+
+// token account owner is the order book or has an active investor account
+fn is_permitted_holder(owner: &Pubkey, investor: &AccountInfo, order_book: &Pubkey) -> bool {
+    if owner == order_book {
+        return true;
+    }
+
+    if investor.owner != &crate::ID {
+        return false;
+    }
+
+    match investor.try_borrow_data() {
+        Ok(data) => match Investor::try_deserialize(&mut &data[..]) {
+            Ok(investor) => investor.active,
+            Err(_) => false,
+        },
+        Err(_) => false,
+    }
+}
+
+// transfer authority is an offer of the treasury bonds i.e the delegate of the units on offer
+fn is_offer_of(authority: &AccountInfo, treasury_bonds: &Pubkey) -> bool {
+    if authority.owner != &crate::ID {
+        return false;
+    }
+
+    match authority.try_borrow_data() {
+        Ok(data) => match Offer::try_deserialize(&mut &data[..]) {
+            Ok(offer) => offer.treasury_bonds == *treasury_bonds,
+            Err(_) => false,
+        },
+        Err(_) => false,
+    }
+}
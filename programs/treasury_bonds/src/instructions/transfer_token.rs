@@ -2,11 +2,10 @@
 
 use {
     crate::{error::TreasuryBondsError, state::treasury_bonds::TreasuryBonds},
-    anchor_lang::{prelude::*, system_program},
+    anchor_lang::prelude::*,
     anchor_spl::{
-        associated_token,
         associated_token::AssociatedToken,
-        token::{transfer, Token, TokenAccount, Transfer},
+        token::{transfer, Mint, Token, TokenAccount, Transfer},
     },
 };
 
@@ -14,15 +13,26 @@ use {
 #[instruction(params: TransferTokenParams)]
 pub struct TransferToken<'info> {
     #[account(mut,
-        constraint = treasury_bonds.is_initialized @ TreasuryBondsError::AccountNotInitialized
+        constraint = treasury_bonds.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [
+            b"treasury-bonds",
+            treasury_bonds.owner.as_ref(),
+            treasury_bonds.issue_no.as_bytes()
+        ],
+        bump = treasury_bonds.bump
     )]
     pub treasury_bonds: Account<'info, TreasuryBonds>,
-    #[account(mut)]
+    #[account(mut,
+        constraint = from_account.mint == mint_token.key() @ TreasuryBondsError::InvalidSettlementMint,
+        constraint = from_account.owner == owner.key() @ TreasuryBondsError::InvalidTokenAccountOwner
+    )]
     pub from_account: Account<'info, TokenAccount>,
-    #[account(mut)]
+    #[account(mut,
+        constraint = to_account.mint == mint_token.key() @ TreasuryBondsError::InvalidSettlementMint
+    )]
     pub to_account: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub mint_token: Signer<'info>,
+    #[account(address = treasury_bonds.settlement_mint @ TreasuryBondsError::InvalidSettlementMint)]
+    pub mint_token: Account<'info, Mint>,
     // mut makes it changeble (mutable)
     #[account(mut)]
     pub owner: Signer<'info>,
@@ -43,7 +53,6 @@ pub fn transfer_token(ctx: Context<TransferToken>, params: &TransferTokenParams)
     }
 
     let treasury_bonds = &ctx.accounts.treasury_bonds;
-    let decimals = treasury_bonds.decimals;
     let _amount = params.amount;
 
     let base: u32 = 10;
@@ -0,0 +1,99 @@
+//! UpdateTaxProfile instruction handler
+
+use {
+    crate::{
+        error::TreasuryBondsError,
+        state::{
+            configs::{TreasuryBondsConfigs, ISSUANCE_OPERATOR},
+            proposal::{Proposal, UPDATE_TAX_PROFILE},
+            tax_profile::TaxProfile,
+            treasury_bonds::TreasuryBonds,
+        },
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::TokenAccount,
+};
+
+#[derive(Accounts)]
+pub struct UpdateTaxProfile<'info> {
+    #[account(
+        constraint = treasury_bonds_configs.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        constraint = treasury_bonds_configs.has_role(ISSUANCE_OPERATOR, &owner.key()) @ TreasuryBondsError::MissingRole,
+        seeds = [b"treasury-bonds-configs"],
+        bump
+    )]
+    pub treasury_bonds_configs: Box<Account<'info, TreasuryBondsConfigs>>,
+    // changing the tax profile should be approved by the approvers
+    #[account(mut,
+        constraint = !proposal.is_executed @ TreasuryBondsError::ProposalAlreadyExecuted,
+        constraint = proposal.is_approved(&treasury_bonds_configs) @ TreasuryBondsError::ProposalNotApproved,
+        seeds = [b"proposal", proposal.proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+    #[account(
+        constraint = treasury_bonds.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [
+            b"treasury-bonds",
+            treasury_bonds.owner.as_ref(),
+            treasury_bonds.issue_no.as_bytes()
+        ],
+        bump = treasury_bonds.bump
+    )]
+    pub treasury_bonds: Account<'info, TreasuryBonds>,
+    // mut makes it changeble (mutable)
+    #[account(mut, has_one = treasury_bonds,
+        constraint = tax_profile.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [b"tax-profile", treasury_bonds.key().as_ref()],
+        bump = tax_profile.bump
+    )]
+    pub tax_profile: Account<'info, TaxProfile>,
+    // withheld tax is paid into the tax authority's token account
+    #[account(
+        constraint = tax_authority_tokens.mint == treasury_bonds.settlement_mint @ TreasuryBondsError::InvalidSettlementMint
+    )]
+    pub tax_authority_tokens: Account<'info, TokenAccount>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UpdateTaxProfileParams {
+    pub withholding_tax_rate: Option<u16>, // withholding tax on coupons (basis points), defaults by tenor
+}
+
+// changes the withholding tax rate or tax authority of the treasury bonds once the approvers
+// sign off. the proposal's payload is the serialized params followed by the tax authority's
+// token account
+pub fn update_tax_profile(
+    ctx: Context<UpdateTaxProfile>,
+    params: &UpdateTaxProfileParams,
+) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let treasury_bonds = &ctx.accounts.treasury_bonds;
+    let tax_profile = &mut ctx.accounts.tax_profile;
+    let tax_authority_tokens = &ctx.accounts.tax_authority_tokens;
+
+    // action should be authorized by an approved proposal
+    let now = Clock::get()?.unix_timestamp;
+    if proposal.has_expired(now) {
+        return Err(TreasuryBondsError::ProposalExpired.into());
+    }
+
+    let mut payload = params.try_to_vec()?;
+    payload.extend_from_slice(tax_authority_tokens.key().as_ref());
+
+    if !proposal.authorizes(UPDATE_TAX_PROFILE, &treasury_bonds.key(), &payload) {
+        return Err(TreasuryBondsError::ProposalMismatch.into());
+    }
+
+    proposal.is_executed = true;
+
+    msg!("Validate inputs");
+    tax_profile.apply(
+        treasury_bonds,
+        params.withholding_tax_rate,
+        tax_authority_tokens.key(),
+    )?;
+
+    Ok(())
+}
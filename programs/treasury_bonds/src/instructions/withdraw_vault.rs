@@ -0,0 +1,134 @@
+//! WithdrawVault instruction handler
+
+use {
+    crate::{
+        error::TreasuryBondsError,
+        state::{
+            configs::{TreasuryBondsConfigs, TREASURER},
+            deposit_base::DepositBase,
+            proposal::{Proposal, WITHDRAW_VAULT},
+            treasury_bonds::TreasuryBonds,
+        },
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
+
+#[derive(Accounts)]
+#[instruction(params: WithdrawVaultParams)]
+pub struct WithdrawVault<'info> {
+    #[account(
+        constraint = treasury_bonds_configs.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        constraint = treasury_bonds_configs.has_role(TREASURER, &owner.key()) @ TreasuryBondsError::MissingRole,
+        seeds = [b"treasury-bonds-configs"],
+        bump
+    )]
+    pub treasury_bonds_configs: Box<Account<'info, TreasuryBondsConfigs>>,
+    // withdrawal should be approved by the approvers
+    #[account(mut,
+        constraint = !proposal.is_executed @ TreasuryBondsError::ProposalAlreadyExecuted,
+        constraint = proposal.is_approved(&treasury_bonds_configs) @ TreasuryBondsError::ProposalNotApproved,
+        seeds = [b"proposal", proposal.proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+    #[account(has_one = deposit_account,
+        constraint = treasury_bonds.is_initialized @ TreasuryBondsError::AccountNotInitialized,
+        seeds = [
+            b"treasury-bonds",
+            treasury_bonds.owner.as_ref(),
+            treasury_bonds.issue_no.as_bytes()
+        ],
+        bump = treasury_bonds.bump
+    )]
+    pub treasury_bonds: Box<Account<'info, TreasuryBonds>>,
+    // paid out of the treasury vault
+    #[account(mut,
+        constraint = sender_tokens.key() == treasury_bonds.vault_tokens @ TreasuryBondsError::InvalidVaultAccount
+    )]
+    pub sender_tokens: Account<'info, TokenAccount>,
+    #[account(mut,
+        constraint = recipient_tokens.mint == mint_token.key() @ TreasuryBondsError::InvalidSettlementMint
+    )]
+    pub recipient_tokens: Account<'info, TokenAccount>,
+    #[account(address = treasury_bonds.settlement_mint @ TreasuryBondsError::InvalidSettlementMint)]
+    pub mint_token: Account<'info, Mint>,
+    #[account(
+        constraint = deposit_account.is_initialized @ TreasuryBondsError::AccountNotInitialized
+    )]
+    pub deposit_account: Account<'info, DepositBase>,
+    #[account(seeds = [b"auth", deposit_account.key().as_ref()], bump)]
+    /// CHECK: no need to check this.
+    pub pda_auth: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"treasury-vault", pda_auth.key().as_ref()], bump)]
+    pub treasury_vault: SystemAccount<'info>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct WithdrawVaultParams {
+    pub amount: u32, // amount to withdraw from the treasury vault
+}
+
+// treasurer moves funds out of the treasury vault once the approvers sign off.
+// the proposal's payload is the serialized params followed by the recipient token account
+pub fn withdraw_vault(ctx: Context<WithdrawVault>, params: &WithdrawVaultParams) -> Result<()> {
+    msg!("Validate inputs");
+    if params.amount == 0 {
+        return Err(TreasuryBondsError::InvalidAmount.into());
+    }
+
+    let proposal = &mut ctx.accounts.proposal;
+    let treasury_bonds = &ctx.accounts.treasury_bonds;
+    let sender_tokens = &ctx.accounts.sender_tokens;
+    let recipient_tokens = &ctx.accounts.recipient_tokens;
+    let mint_token = &ctx.accounts.mint_token;
+    let pda_auth = &ctx.accounts.pda_auth;
+    let treasury_vault = &ctx.accounts.treasury_vault;
+    let token_program = &ctx.accounts.token_program;
+    let decimals: u8 = treasury_bonds.decimals;
+
+    // action should be authorized by an approved proposal
+    let now = Clock::get()?.unix_timestamp;
+    if proposal.has_expired(now) {
+        return Err(TreasuryBondsError::ProposalExpired.into());
+    }
+
+    let mut payload = params.try_to_vec()?;
+    payload.extend_from_slice(recipient_tokens.key().as_ref());
+
+    if !proposal.authorizes(WITHDRAW_VAULT, &treasury_bonds.key(), &payload) {
+        return Err(TreasuryBondsError::ProposalMismatch.into());
+    }
+
+    proposal.is_executed = true;
+
+    let _amount = treasury_bonds
+        .face_value_of(params.amount)
+        .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+    // Transfer funds from treasury vault to recipient
+    let cpi_accounts = TransferChecked {
+        from: sender_tokens.to_account_info(),
+        mint: mint_token.to_account_info(),
+        to: recipient_tokens.to_account_info(),
+        authority: treasury_vault.to_account_info(),
+    };
+
+    let seeds = &[
+        b"treasury-vault",
+        pda_auth.to_account_info().key.as_ref(),
+        &[ctx.bumps.treasury_vault],
+    ];
+
+    let signer = &[&seeds[..]];
+
+    let cpi = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer);
+
+    transfer_checked(cpi, _amount, decimals)?;
+
+    Ok(())
+}
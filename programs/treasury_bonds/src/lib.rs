@@ -1,7 +1,10 @@
 //! treasury_bonds program entrypoint
 
+pub mod bond_units;
 pub mod error;
+pub mod events;
 pub mod instructions;
+pub mod pricing;
 pub mod state;
 
 use {anchor_lang::prelude::*, instructions::*};
@@ -13,8 +16,16 @@ pub mod treasury_bonds {
     use super::*;
 
     // admin instructions
-    pub fn init(ctx: Context<Init>) -> Result<()> {
-        instructions::init(ctx)
+    pub fn init(ctx: Context<Init>, params: InitParams) -> Result<()> {
+        instructions::init(ctx, &params)
+    }
+
+    pub fn transfer_admin(ctx: Context<TransferAdmin>, params: TransferAdminParams) -> Result<()> {
+        instructions::transfer_admin(ctx, &params)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        instructions::accept_admin(ctx)
     }
 
     pub fn register_treasury_bonds(
@@ -24,6 +35,102 @@ pub mod treasury_bonds {
         instructions::register_treasury_bonds(ctx, &params)
     }
 
+    pub fn declare_maturity(ctx: Context<DeclareMaturity>) -> Result<()> {
+        instructions::declare_maturity(ctx)
+    }
+
+    pub fn migrate_treasury_bonds(
+        ctx: Context<MigrateTreasuryBonds>,
+        params: MigrateTreasuryBondsParams,
+    ) -> Result<()> {
+        instructions::migrate_treasury_bonds(ctx, &params)
+    }
+
+    pub fn migrate_coupon_rate(ctx: Context<MigrateCouponRate>) -> Result<()> {
+        instructions::migrate_coupon_rate(ctx)
+    }
+
+    pub fn migrate_investor(ctx: Context<MigrateInvestor>) -> Result<()> {
+        instructions::migrate_investor(ctx)
+    }
+
+    pub fn open_auction(ctx: Context<OpenAuction>, params: OpenAuctionParams) -> Result<()> {
+        instructions::open_auction(ctx, &params)
+    }
+
+    pub fn close_auction(ctx: Context<CloseAuction>) -> Result<()> {
+        instructions::close_auction(ctx)
+    }
+
+    pub fn open_order_book(ctx: Context<OpenOrderBook>) -> Result<()> {
+        instructions::open_order_book(ctx)
+    }
+
+    pub fn evict_order(ctx: Context<EvictOrder>) -> Result<()> {
+        instructions::evict_order(ctx)
+    }
+
+    pub fn add_settlement_mint(ctx: Context<AddSettlementMint>) -> Result<()> {
+        instructions::add_settlement_mint(ctx)
+    }
+
+    pub fn remove_settlement_mint(
+        ctx: Context<RemoveSettlementMint>,
+        params: RemoveSettlementMintParams,
+    ) -> Result<()> {
+        instructions::remove_settlement_mint(ctx, &params)
+    }
+
+    pub fn grant_role(ctx: Context<GrantRole>, params: GrantRoleParams) -> Result<()> {
+        instructions::grant_role(ctx, &params)
+    }
+
+    pub fn revoke_role(ctx: Context<RevokeRole>, params: RevokeRoleParams) -> Result<()> {
+        instructions::revoke_role(ctx, &params)
+    }
+
+    pub fn fund_vault(ctx: Context<FundVault>, params: FundVaultParams) -> Result<()> {
+        instructions::fund_vault(ctx, &params)
+    }
+
+    pub fn set_investor_status(
+        ctx: Context<SetInvestorStatus>,
+        params: SetInvestorStatusParams,
+    ) -> Result<()> {
+        instructions::set_investor_status(ctx, &params)
+    }
+
+    pub fn withdraw_vault(ctx: Context<WithdrawVault>, params: WithdrawVaultParams) -> Result<()> {
+        instructions::withdraw_vault(ctx, &params)
+    }
+
+    // proposals
+    pub fn set_approvers(ctx: Context<SetApprovers>, params: SetApproversParams) -> Result<()> {
+        instructions::set_approvers(ctx, &params)
+    }
+
+    pub fn create_proposal(
+        ctx: Context<CreateProposal>,
+        params: CreateProposalParams,
+    ) -> Result<()> {
+        instructions::create_proposal(ctx, &params)
+    }
+
+    pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+        instructions::approve_proposal(ctx)
+    }
+
+    pub fn set_tax_profile(ctx: Context<SetTaxProfile>, params: SetTaxProfileParams) -> Result<()> {
+        instructions::set_tax_profile(ctx, &params)
+    }
+
+    pub fn update_tax_profile(
+        ctx: Context<UpdateTaxProfile>,
+        params: UpdateTaxProfileParams,
+    ) -> Result<()> {
+        instructions::update_tax_profile(ctx, &params)
+    }
+
     // public instructions
     pub fn register_investor(
         ctx: Context<RegisterInvestor>,
@@ -39,13 +146,36 @@ pub mod treasury_bonds {
         instructions::buy_treasury_bonds(ctx, &params)
     }
 
-    pub fn sell_treasury_bonds(
-        ctx: Context<SellTreasuryBonds>,
+    pub fn create_offer(ctx: Context<CreateOffer>, params: CreateOfferParams) -> Result<()> {
+        instructions::create_offer(ctx, &params)
+    }
+
+    pub fn cancel_offer(ctx: Context<CancelOffer>) -> Result<()> {
+        instructions::cancel_offer(ctx)
+    }
+
+    pub fn sell_treasury_bonds<'info>(
+        ctx: Context<'_, '_, '_, 'info, SellTreasuryBonds<'info>>,
         params: SellTreasuryBondsParams,
     ) -> Result<()> {
         instructions::sell_treasury_bonds(ctx, &params)
     }
 
+    pub fn place_order<'info>(
+        ctx: Context<'_, '_, '_, 'info, PlaceOrder<'info>>,
+        params: PlaceOrderParams,
+    ) -> Result<()> {
+        instructions::place_order(ctx, &params)
+    }
+
+    pub fn cancel_order<'info>(ctx: Context<'_, '_, '_, 'info, CancelOrder<'info>>) -> Result<()> {
+        instructions::cancel_order(ctx)
+    }
+
+    pub fn match_orders<'info>(ctx: Context<'_, '_, '_, 'info, MatchOrders<'info>>) -> Result<()> {
+        instructions::match_orders(ctx)
+    }
+
     pub fn create_token(ctx: Context<CreateToken>, params: CreateTokenParams) -> Result<()> {
         instructions::create_token(ctx, &params)
     }
@@ -60,4 +190,33 @@ pub mod treasury_bonds {
     ) -> Result<()> {
         instructions::redeem_treasury_bonds(ctx, &params)
     }
+
+    pub fn repay_principal(ctx: Context<RepayPrincipal>) -> Result<()> {
+        instructions::repay_principal(ctx)
+    }
+
+    pub fn submit_bid(ctx: Context<SubmitBid>, params: SubmitBidParams) -> Result<()> {
+        instructions::submit_bid(ctx, &params)
+    }
+
+    pub fn settle_bid(ctx: Context<SettleBid>) -> Result<()> {
+        instructions::settle_bid(ctx)
+    }
+
+    pub fn pay_coupon(ctx: Context<PayCoupon>) -> Result<()> {
+        instructions::pay_coupon(ctx)
+    }
+
+    pub fn quote_price_yield(
+        ctx: Context<QuotePriceYield>,
+        params: QuotePriceYieldParams,
+    ) -> Result<PriceYieldQuote> {
+        instructions::quote_price_yield(ctx, &params)
+    }
+
+    // transfer hook
+    #[interface(spl_transfer_hook_interface::execute)]
+    pub fn transfer_hook(ctx: Context<TransferHook>, amount: u64) -> Result<()> {
+        instructions::transfer_hook(ctx, amount)
+    }
 }
@@ -0,0 +1,528 @@
+//! Bond pricing i.e day-count conventions and accrued interest.
+//! Kept free of account types so that it can be unit tested off-chain.
+
+pub mod yield_to_maturity;
+
+use yield_to_maturity::FIXED_POINT_SCALE;
+
+// day-count conventions
+pub const ACTUAL_365: u8 = 1;
+pub const ACTUAL_ACTUAL_ICMA: u8 = 2;
+pub const THIRTY_360: u8 = 3;
+
+// basis points in one (100%)
+pub const BASIS_POINTS: u64 = 10_000;
+// scale of coupon rates i.e ten-thousandths of a percent, 1_000_000 = 100% and 129_655 = 12.9655%
+pub const COUPON_RATE_SCALE: u64 = 1_000_000;
+// seconds in a day
+pub const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+// coupon rate of a whole percentage i.e 12% = 120_000
+pub fn percent_to_coupon_rate(percent: u8) -> u32 {
+    (percent as u64 * COUPON_RATE_SCALE / 100) as u32
+}
+
+pub fn is_valid_day_count(day_count: u8) -> bool {
+    matches!(day_count, ACTUAL_365 | ACTUAL_ACTUAL_ICMA | THIRTY_360)
+}
+
+// fraction of a year between the start and end dates (unix timestamps) as (days, days in year).
+// actual/actual (ICMA) measures the fraction against the coupon period containing the dates
+// i.e days / (coupon frequency * days in the coupon period).
+pub fn year_fraction(
+    day_count: u8,
+    start: i64,
+    end: i64,
+    period_start: i64,
+    period_end: i64,
+    frequency: u64,
+) -> Option<(u64, u64)> {
+    if end < start {
+        return None;
+    }
+
+    match day_count {
+        ACTUAL_365 => Some((actual_days(start, end)?, 365)),
+        ACTUAL_ACTUAL_ICMA => {
+            let period_days = actual_days(period_start, period_end)?;
+            if period_days == 0 {
+                return None;
+            }
+
+            Some((
+                actual_days(start, end)?,
+                frequency.checked_mul(period_days)?,
+            ))
+        }
+        THIRTY_360 => Some((thirty_360_days(start, end)?, 360)),
+        _ => None,
+    }
+}
+
+// interest accrued on the face value (in smallest unit) from the start of the coupon period
+// to the settlement date i.e face value * coupon rate * year fraction (rounded down)
+pub fn accrued_interest(
+    face_value: u64,
+    coupon_rate: u32,
+    day_count: u8,
+    period_start: i64,
+    period_end: i64,
+    settlement_date: i64,
+    frequency: u64,
+) -> Option<u64> {
+    let settlement_date = settlement_date.clamp(period_start, period_end);
+    let (days, days_in_year) = year_fraction(
+        day_count,
+        period_start,
+        settlement_date,
+        period_start,
+        period_end,
+        frequency,
+    )?;
+
+    let interest = (face_value as u128)
+        .checked_mul(coupon_rate as u128)?
+        .checked_mul(days as u128)?
+        .checked_div((COUPON_RATE_SCALE as u128).checked_mul(days_in_year as u128)?)?;
+
+    u64::try_from(interest).ok()
+}
+
+// coupon paid on the face value (in smallest unit) for a whole coupon period
+pub fn coupon_payment(
+    face_value: u64,
+    coupon_rate: u32,
+    day_count: u8,
+    period_start: i64,
+    period_end: i64,
+    frequency: u64,
+) -> Option<u64> {
+    accrued_interest(
+        face_value,
+        coupon_rate,
+        day_count,
+        period_start,
+        period_end,
+        period_end,
+        frequency,
+    )
+}
+
+// discount periods of a bond paying no coupons as at the settlement date (unix timestamp),
+// periods of the given calendar months being stepped back from the redemption date like coupon
+// dates. returns the periods left to run, the current one included, and the fraction of the
+// current period elapsed (fixed-point). the fraction is measured against a whole period, so that
+// a short first period is only discounted for the time left to run. settlement before the value
+// date is treated as settlement on the value date.
+pub fn discount_periods(
+    value_date: i64,
+    redemption_date: i64,
+    settlement_date: i64,
+    months: i64,
+) -> Option<(u32, u128)> {
+    let settlement_date = settlement_date.max(value_date);
+    if months <= 0 || settlement_date >= redemption_date {
+        return None;
+    }
+
+    // periods ending after the settlement date
+    let mut periods: u32 = 1;
+    while add_months(redemption_date, -(periods as i64) * months) > settlement_date {
+        periods = periods.checked_add(1)?;
+    }
+
+    let period_start = add_months(redemption_date, -(periods as i64) * months);
+    let period_end = add_months(redemption_date, -(periods as i64 - 1) * months);
+    let accrued_fraction = (settlement_date.checked_sub(period_start)? as u128)
+        .checked_mul(FIXED_POINT_SCALE)?
+        .checked_div(period_end.checked_sub(period_start)? as u128)?;
+
+    Some((periods, accrued_fraction))
+}
+
+// actual number of days between two unix timestamps
+fn actual_days(start: i64, end: i64) -> Option<u64> {
+    let days = end
+        .div_euclid(SECONDS_PER_DAY)
+        .checked_sub(start.div_euclid(SECONDS_PER_DAY))?;
+
+    u64::try_from(days).ok()
+}
+
+// number of days between two unix timestamps with 30 day months and 360 day years
+// (30/360 bond basis i.e the 31st is treated as the 30th)
+fn thirty_360_days(start: i64, end: i64) -> Option<u64> {
+    let (year1, month1, day1) = civil_from_days(start.div_euclid(SECONDS_PER_DAY));
+    let (year2, month2, day2) = civil_from_days(end.div_euclid(SECONDS_PER_DAY));
+
+    let day1 = day1.min(30);
+    let day2 = if day2 == 31 && day1 == 30 { 30 } else { day2 };
+
+    let days = 360 * (year2 - year1) + 30 * (month2 - month1) + (day2 - day1);
+
+    u64::try_from(days).ok()
+}
+
+// unix timestamp the given number of calendar months after (before, if negative) the given
+// unix timestamp, the day is moved back to the end of shorter months i.e 31-08 less six months is 28-02
+pub fn add_months(timestamp: i64, months: i64) -> i64 {
+    let (year, month, day) = civil_from_days(timestamp.div_euclid(SECONDS_PER_DAY));
+    let month_index = year * 12 + (month - 1) + months;
+    let year = month_index.div_euclid(12);
+    let month = month_index.rem_euclid(12) + 1;
+    let day = day.min(days_in_month(year, month));
+
+    days_from_civil(year, month, day) * SECONDS_PER_DAY + timestamp.rem_euclid(SECONDS_PER_DAY)
+}
+
+pub fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// number of days since 1970-01-01 for the given civil date (proleptic Gregorian calendar)
+pub fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_index = (month + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+// civil date (year, month, day) of the given number of days since 1970-01-01
+pub fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // coupons are paid semi-annually
+    const FREQUENCY: u64 = 2;
+
+    fn date(year: i64, month: i64, day: i64) -> i64 {
+        days_from_civil(year, month, day) * SECONDS_PER_DAY
+    }
+
+    #[test]
+    fn civil_dates_round_trip() {
+        for days in [-1, 0, 59, 365, 11_016, 19_782, 20_000, 47_540] {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days_from_civil(year, month, day), days);
+        }
+
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(days_from_civil(2024, 2, 29)), (2024, 2, 29));
+    }
+
+    #[test]
+    fn calendar_months_are_stepped_from_the_same_date() {
+        assert_eq!(add_months(date(2024, 5, 15), -6), date(2023, 11, 15));
+        assert_eq!(add_months(date(2024, 5, 15), 6 * 60), date(2054, 5, 15));
+
+        // month ends are moved back to the end of shorter months
+        assert_eq!(add_months(date(2024, 8, 31), -6), date(2024, 2, 29));
+        assert_eq!(add_months(date(2025, 8, 31), -6), date(2025, 2, 28));
+        assert_eq!(add_months(date(2024, 8, 31), -2), date(2024, 6, 30));
+
+        // time of day is kept
+        assert_eq!(
+            add_months(date(2024, 1, 15) + 3600, 1),
+            date(2024, 2, 15) + 3600
+        );
+    }
+
+    // worked examples of "EMU and Market Conventions: Recent Developments" (ISDA, 1998),
+    // section on the actual/actual day count fraction
+
+    #[test]
+    fn isda_1998_example_1_regular_coupon_period() {
+        // semi-annual coupon period from 01-11-2003 to 01-05-2004 i.e 61 days in 2003 and 121 days in 2004
+        let period_start = date(2003, 11, 1);
+        let period_end = date(2004, 5, 1);
+
+        // ISMA: 182 / (182 * 2) = 0.5
+        let fraction = year_fraction(
+            ACTUAL_ACTUAL_ICMA,
+            period_start,
+            period_end,
+            period_start,
+            period_end,
+            FREQUENCY,
+        );
+        assert_eq!(fraction, Some((182, 364)));
+
+        // actual days of the example over a fixed 365 day year
+        let fraction = year_fraction(
+            ACTUAL_365,
+            period_start,
+            period_end,
+            period_start,
+            period_end,
+            FREQUENCY,
+        );
+        assert_eq!(fraction, Some((182, 365)));
+    }
+
+    #[test]
+    fn isda_1998_example_2_short_first_coupon_period() {
+        // annual coupons, short first coupon period from 01-02-1999 to 01-07-1999 measured
+        // against the notional coupon period from 01-07-1998 to 01-07-1999
+        // ISMA: 150 / (365 * 1) = 0.41096
+        let fraction = year_fraction(
+            ACTUAL_ACTUAL_ICMA,
+            date(1999, 2, 1),
+            date(1999, 7, 1),
+            date(1998, 7, 1),
+            date(1999, 7, 1),
+            1,
+        );
+        assert_eq!(fraction, Some((150, 365)));
+    }
+
+    #[test]
+    fn isda_2006_section_4_16_f_thirty_360() {
+        // 360 * (Y2 - Y1) + 30 * (M2 - M1) + (D2 - D1), D1 of 31 is changed to 30
+        // and D2 of 31 is changed to 30 when D1 is 30 or 31
+        let fraction = year_fraction(
+            THIRTY_360,
+            date(2024, 1, 31),
+            date(2024, 3, 31),
+            date(2024, 1, 31),
+            date(2024, 7, 31),
+            FREQUENCY,
+        );
+        assert_eq!(fraction, Some((60, 360)));
+
+        // D2 of 31 is kept when D1 is before the 30th
+        let fraction = year_fraction(
+            THIRTY_360,
+            date(2024, 1, 15),
+            date(2024, 3, 31),
+            date(2024, 1, 15),
+            date(2024, 7, 15),
+            FREQUENCY,
+        );
+        assert_eq!(fraction, Some((76, 360)));
+
+        // february is counted as 30 days
+        let fraction = year_fraction(
+            THIRTY_360,
+            date(2024, 2, 29),
+            date(2024, 8, 29),
+            date(2024, 2, 29),
+            date(2024, 8, 29),
+            FREQUENCY,
+        );
+        assert_eq!(fraction, Some((180, 360)));
+    }
+
+    #[test]
+    fn accrued_interest_over_the_isda_1998_example_1_period() {
+        // 1,000,000 face value at a 12.5% coupon, accrued to 01-02-2004 i.e 92 days into
+        // the coupon period from 01-11-2003 to 01-05-2004
+        let face_value = 1_000_000;
+        let period_start = date(2003, 11, 1);
+        let period_end = date(2004, 5, 1);
+        let settlement_date = date(2004, 2, 1);
+
+        // ISMA: 1,000,000 * 12.5% * 92 / (182 * 2) = 31,593.40
+        let accrued = accrued_interest(
+            face_value,
+            125_000,
+            ACTUAL_ACTUAL_ICMA,
+            period_start,
+            period_end,
+            settlement_date,
+            FREQUENCY,
+        );
+        assert_eq!(accrued, Some(31_593));
+
+        // 1,000,000 * 12.5% * 92 / 365 = 31,506.84
+        let accrued = accrued_interest(
+            face_value,
+            125_000,
+            ACTUAL_365,
+            period_start,
+            period_end,
+            settlement_date,
+            FREQUENCY,
+        );
+        assert_eq!(accrued, Some(31_506));
+
+        // 1,000,000 * 12.5% * 90 / 360 = 31,250
+        let accrued = accrued_interest(
+            face_value,
+            125_000,
+            THIRTY_360,
+            period_start,
+            period_end,
+            settlement_date,
+            FREQUENCY,
+        );
+        assert_eq!(accrued, Some(31_250));
+    }
+
+    #[test]
+    fn accrued_interest_is_bounded_by_the_coupon_period() {
+        let period_start = date(2024, 1, 15);
+        let period_end = date(2024, 7, 15);
+
+        let accrued = accrued_interest(
+            1_000_000,
+            125_000,
+            ACTUAL_ACTUAL_ICMA,
+            period_start,
+            period_end,
+            period_start,
+            FREQUENCY,
+        );
+        assert_eq!(accrued, Some(0));
+
+        let accrued = accrued_interest(
+            1_000_000,
+            125_000,
+            ACTUAL_ACTUAL_ICMA,
+            period_start,
+            period_end,
+            date(2024, 9, 1),
+            FREQUENCY,
+        );
+        assert_eq!(accrued, Some(62_500));
+    }
+
+    #[test]
+    fn coupon_payment_for_a_whole_period() {
+        // 182 day coupon period from the value date
+        let period_start = date(2024, 1, 15);
+        let period_end = period_start + 182 * SECONDS_PER_DAY;
+
+        // half the annual coupon under actual/actual (ICMA) i.e 1,000,000 * 13.5% / 2
+        let coupon = coupon_payment(
+            1_000_000,
+            135_000,
+            ACTUAL_ACTUAL_ICMA,
+            period_start,
+            period_end,
+            FREQUENCY,
+        );
+        assert_eq!(coupon, Some(67_500));
+
+        // 1,000,000 * 13.5% * 182 / 365 = 67,315.07
+        let coupon = coupon_payment(
+            1_000_000,
+            135_000,
+            ACTUAL_365,
+            period_start,
+            period_end,
+            FREQUENCY,
+        );
+        assert_eq!(coupon, Some(67_315));
+    }
+
+    #[test]
+    fn fractional_coupon_rate() {
+        // 12.9655% coupon i.e 1,000,000 * 12.9655% / 2 = 64,827.50
+        let period_start = date(2024, 1, 15);
+        let period_end = period_start + 182 * SECONDS_PER_DAY;
+
+        let coupon = coupon_payment(
+            1_000_000,
+            129_655,
+            ACTUAL_ACTUAL_ICMA,
+            period_start,
+            period_end,
+            FREQUENCY,
+        );
+        assert_eq!(coupon, Some(64_827));
+
+        assert_eq!(percent_to_coupon_rate(12), 120_000);
+    }
+
+    #[test]
+    fn discount_periods_are_stepped_back_from_the_redemption_date() {
+        // 5 year bond from 15-05-2024 to 15-05-2029 i.e 10 half-yearly periods
+        let value_date = date(2024, 5, 15);
+        let redemption_date = date(2029, 5, 15);
+
+        // at issue, and before it
+        assert_eq!(
+            discount_periods(value_date, redemption_date, value_date, 6),
+            Some((10, 0))
+        );
+        assert_eq!(
+            discount_periods(value_date, redemption_date, date(2024, 5, 1), 6),
+            Some((10, 0))
+        );
+
+        // 92 days into the 184 day period from 15-05-2024 to 15-11-2024
+        assert_eq!(
+            discount_periods(value_date, redemption_date, date(2024, 8, 15), 6),
+            Some((10, FIXED_POINT_SCALE / 2))
+        );
+
+        // on a period date, the next period is yet to start
+        assert_eq!(
+            discount_periods(value_date, redemption_date, date(2026, 11, 15), 6),
+            Some((5, 0))
+        );
+
+        // a day before redemption, 180 days into the 181 day period from 15-11-2028
+        let (periods, accrued_fraction) =
+            discount_periods(value_date, redemption_date, date(2029, 5, 14), 6).unwrap();
+        assert_eq!(periods, 1);
+        assert_eq!(accrued_fraction, FIXED_POINT_SCALE * 180 / 181);
+
+        // nothing is left to discount from redemption
+        assert_eq!(
+            discount_periods(value_date, redemption_date, redemption_date, 6),
+            None
+        );
+    }
+
+    #[test]
+    fn short_first_discount_period() {
+        // redemption date adjusted to the next business day, a day after the 5 years
+        let value_date = date(2024, 5, 15);
+        let redemption_date = date(2029, 5, 16);
+
+        // the first period from 15-05-2024 is a day short of the period from 16-11-2023
+        let (periods, accrued_fraction) =
+            discount_periods(value_date, redemption_date, value_date, 6).unwrap();
+        assert_eq!(periods, 11);
+        assert_eq!(accrued_fraction, FIXED_POINT_SCALE * 181 / 182);
+    }
+
+    #[test]
+    fn unknown_day_count_is_rejected() {
+        assert!(!is_valid_day_count(0));
+        assert!(!is_valid_day_count(4));
+        assert_eq!(
+            year_fraction(0, 0, SECONDS_PER_DAY, 0, SECONDS_PER_DAY, 2),
+            None
+        );
+    }
+}
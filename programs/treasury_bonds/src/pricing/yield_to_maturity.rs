@@ -0,0 +1,320 @@
+//! Price and yield to maturity of fixed coupon, amortizing and zero coupon bonds,
+//! in fixed-point arithmetic so that results are deterministic on-chain.
+//!
+//! Cash flows are discounted at the yield compounded once per coupon period. The part of the
+//! current coupon period still to run is discounted with simple interest, which keeps the
+//! calculation free of fractional powers.
+
+use crate::pricing::{BASIS_POINTS, COUPON_RATE_SCALE};
+
+// fixed-point scale of rates, prices and cash flows i.e 1_000_000_000_000 = 1 (100%)
+pub const FIXED_POINT_SCALE: u128 = 1_000_000_000_000;
+// yields are solved for between 0% and 100% a year
+pub const MAX_YIELD: u128 = FIXED_POINT_SCALE;
+// bounds the work of the solver, it usually converges within a handful of iterations
+const MAX_SOLVER_ITERATIONS: u32 = 32;
+// yields closer than this are considered equal i.e 0.00000001%
+const YIELD_TOLERANCE: u128 = 100;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BondCashFlows {
+    pub frequency: u64,         // coupon periods per year
+    pub accrued_fraction: u128, // fraction of the current coupon period elapsed (fixed-point)
+    pub current_coupon: u128,   // coupon of the current period per unit of face value (fixed-point)
+    pub amounts: Vec<u128>, // cash flow at the end of each remaining period per unit of face value (fixed-point)
+}
+
+impl BondCashFlows {
+    // coupons every period and the face value repaid with the last coupon
+    pub fn fixed_coupon(
+        coupon_rate: u32,
+        frequency: u64,
+        periods: u32,
+        accrued_fraction: u128,
+    ) -> Option<Self> {
+        if periods == 0 {
+            return None;
+        }
+
+        let mut principal_repayments_bps = vec![0; periods as usize];
+        principal_repayments_bps[periods as usize - 1] = BASIS_POINTS as u32;
+
+        Self::amortizing(
+            coupon_rate,
+            frequency,
+            &principal_repayments_bps,
+            accrued_fraction,
+        )
+    }
+
+    // face value repaid at maturity without any coupons
+    pub fn zero_coupon(frequency: u64, periods: u32, accrued_fraction: u128) -> Option<Self> {
+        Self::fixed_coupon(0, frequency, periods, accrued_fraction)
+    }
+
+    // face value repaid in instalments, each remaining period repaying the given share (basis
+    // points) of the outstanding face value. coupons are paid on the face value outstanding
+    // at the start of each period.
+    pub fn amortizing(
+        coupon_rate: u32,
+        frequency: u64,
+        principal_repayments_bps: &[u32],
+        accrued_fraction: u128,
+    ) -> Option<Self> {
+        if frequency == 0
+            || principal_repayments_bps.is_empty()
+            || accrued_fraction > FIXED_POINT_SCALE
+        {
+            return None;
+        }
+
+        let total_repayments_bps = principal_repayments_bps
+            .iter()
+            .try_fold(0u64, |total, repayment| {
+                total.checked_add(*repayment as u64)
+            })?;
+        if total_repayments_bps != BASIS_POINTS {
+            return None;
+        }
+
+        // coupon per period per unit of face value
+        let coupon_rate = (coupon_rate as u128)
+            .checked_mul(FIXED_POINT_SCALE)?
+            .checked_div((COUPON_RATE_SCALE as u128).checked_mul(frequency as u128)?)?;
+
+        let mut outstanding = FIXED_POINT_SCALE;
+        let mut amounts = Vec::with_capacity(principal_repayments_bps.len());
+        for repayment_bps in principal_repayments_bps {
+            let principal = (*repayment_bps as u128)
+                .checked_mul(FIXED_POINT_SCALE)?
+                .checked_div(BASIS_POINTS as u128)?;
+            let coupon = mul(outstanding, coupon_rate)?;
+
+            amounts.push(coupon.checked_add(principal)?);
+            outstanding = outstanding.checked_sub(principal)?;
+        }
+
+        Some(Self {
+            frequency,
+            accrued_fraction,
+            current_coupon: coupon_rate,
+            amounts,
+        })
+    }
+
+    // coupon accrued in the current period per unit of face value
+    pub fn accrued_interest(&self) -> Option<u128> {
+        mul(self.current_coupon, self.accrued_fraction)
+    }
+
+    // price including accrued interest per unit of face value at the given annual yield
+    pub fn dirty_price(&self, yield_rate: u128) -> Option<u128> {
+        Some(self.present_value(yield_rate)?.0)
+    }
+
+    // price excluding accrued interest per unit of face value at the given annual yield
+    pub fn clean_price(&self, yield_rate: u128) -> Option<u128> {
+        self.dirty_price(yield_rate)?
+            .checked_sub(self.accrued_interest()?)
+    }
+
+    // annual yield at which the cash flows are worth the given clean price
+    pub fn yield_from_clean_price(&self, clean_price: u128) -> Option<u128> {
+        self.yield_from_dirty_price(clean_price.checked_add(self.accrued_interest()?)?)
+    }
+
+    // annual yield at which the cash flows are worth the given dirty price.
+    // newton's method, falling back to bisection whenever a step leaves the bracket.
+    pub fn yield_from_dirty_price(&self, dirty_price: u128) -> Option<u128> {
+        let mut low: u128 = 0;
+        let mut high: u128 = MAX_YIELD;
+
+        // yields outside 0% - 100% are not solved for
+        if dirty_price > self.dirty_price(low)? || dirty_price < self.dirty_price(high)? {
+            return None;
+        }
+
+        let mut yield_rate = (low + high) / 10;
+        for _ in 0..MAX_SOLVER_ITERATIONS {
+            let (price, slope) = self.present_value(yield_rate)?;
+
+            // price falls as the yield rises
+            if price > dirty_price {
+                low = yield_rate;
+            } else {
+                high = yield_rate;
+            }
+
+            let step = if slope == 0 {
+                0
+            } else {
+                div(price.abs_diff(dirty_price), slope)?
+            };
+
+            let mut next_yield_rate = if price > dirty_price {
+                yield_rate.checked_add(step)?
+            } else {
+                yield_rate.saturating_sub(step)
+            };
+
+            if next_yield_rate <= low || next_yield_rate >= high {
+                next_yield_rate = (low + high) / 2;
+            }
+
+            if next_yield_rate.abs_diff(yield_rate) <= YIELD_TOLERANCE {
+                return Some(next_yield_rate);
+            }
+
+            yield_rate = next_yield_rate;
+        }
+
+        Some(yield_rate)
+    }
+
+    // dirty price and the rate at which it falls per unit rise of the annual yield.
+    // price = sum of cash flows * v ^ (k - 1) / (1 + remaining fraction * r),
+    // where r is the yield per period and v = 1 / (1 + r)
+    fn present_value(&self, yield_rate: u128) -> Option<(u128, u128)> {
+        let rate = yield_rate.checked_div(self.frequency as u128)?;
+        let discount_factor = div(FIXED_POINT_SCALE, FIXED_POINT_SCALE.checked_add(rate)?)?;
+
+        // horner's method for the sum of discounted cash flows and its derivative
+        let mut sum: u128 = 0;
+        let mut derivative: u128 = 0;
+        for amount in self.amounts.iter().rev() {
+            derivative = mul(derivative, discount_factor)?.checked_add(sum)?;
+            sum = mul(sum, discount_factor)?.checked_add(*amount)?;
+        }
+
+        let remaining_fraction = FIXED_POINT_SCALE.checked_sub(self.accrued_fraction)?;
+        let denominator = FIXED_POINT_SCALE.checked_add(mul(remaining_fraction, rate)?)?;
+        let price = div(sum, denominator)?;
+
+        // d(price)/d(rate) = -(derivative * v ^ 2 * denominator + sum * remaining fraction) / denominator ^ 2
+        let numerator = mul(
+            mul(mul(derivative, discount_factor)?, discount_factor)?,
+            denominator,
+        )?
+        .checked_add(mul(sum, remaining_fraction)?)?;
+        let slope =
+            div(numerator, mul(denominator, denominator)?)?.checked_div(self.frequency as u128)?;
+
+        Some((price, slope))
+    }
+}
+
+// fixed-point multiplication i.e a * b
+fn mul(a: u128, b: u128) -> Option<u128> {
+    a.checked_mul(b)?.checked_div(FIXED_POINT_SCALE)
+}
+
+// fixed-point division i.e a / b
+fn div(a: u128, b: u128) -> Option<u128> {
+    a.checked_mul(FIXED_POINT_SCALE)?.checked_div(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // coupons are paid semi-annually
+    const FREQUENCY: u64 = 2;
+
+    // fixed-point value of the given basis points
+    fn bps(value: u128) -> u128 {
+        value * FIXED_POINT_SCALE / BASIS_POINTS as u128
+    }
+
+    fn assert_close(actual: u128, expected: u128, tolerance: u128) {
+        assert!(
+            actual.abs_diff(expected) <= tolerance,
+            "{} is not within {} of {}",
+            actual,
+            tolerance,
+            expected
+        );
+    }
+
+    #[test]
+    fn bond_priced_at_its_coupon_rate_is_at_par() {
+        let cash_flows = BondCashFlows::fixed_coupon(125_000, FREQUENCY, 20, 0).unwrap();
+
+        let price = cash_flows.clean_price(bps(1250)).unwrap();
+        assert_close(price, FIXED_POINT_SCALE, 1_000);
+
+        let yield_rate = cash_flows
+            .yield_from_clean_price(FIXED_POINT_SCALE)
+            .unwrap();
+        assert_close(yield_rate, bps(1250), 1_000);
+    }
+
+    #[test]
+    fn fixed_coupon_bond_at_a_discount() {
+        // 10 year bond paying 12% semi-annually, priced to yield 14%
+        // price = 6 * (1 - 1.07 ^ -20) / 0.07 + 100 * 1.07 ^ -20 = 89.4060 per 100
+        let cash_flows = BondCashFlows::fixed_coupon(120_000, FREQUENCY, 20, 0).unwrap();
+
+        let price = cash_flows.clean_price(bps(1400)).unwrap();
+        assert_close(price, 894_059_857_545, 1_000_000);
+
+        let yield_rate = cash_flows.yield_from_clean_price(price).unwrap();
+        assert_close(yield_rate, bps(1400), 1_000);
+    }
+
+    #[test]
+    fn zero_coupon_bond() {
+        // 2 years to maturity at 10% i.e 100 / 1.05 ^ 4 = 82.2702 per 100
+        let cash_flows = BondCashFlows::zero_coupon(FREQUENCY, 4, 0).unwrap();
+
+        let price = cash_flows.dirty_price(bps(1000)).unwrap();
+        assert_close(price, 822_702_474_792, 1_000_000);
+
+        let yield_rate = cash_flows.yield_from_dirty_price(price).unwrap();
+        assert_close(yield_rate, bps(1000), 1_000);
+    }
+
+    #[test]
+    fn amortizing_bond() {
+        // face value repaid in two halves, 10% coupon paid semi-annually, priced to yield 10%
+        let cash_flows =
+            BondCashFlows::amortizing(100_000, FREQUENCY, &[0, 5000, 5000], 0).unwrap();
+
+        assert_eq!(
+            cash_flows.amounts,
+            vec![bps(500), bps(500) + bps(5000), bps(250) + bps(5000)]
+        );
+
+        let price = cash_flows.clean_price(bps(1000)).unwrap();
+        assert_close(price, FIXED_POINT_SCALE, 1_000);
+
+        // repayments should cover the whole face value
+        assert_eq!(
+            BondCashFlows::amortizing(100_000, FREQUENCY, &[5000, 4000], 0),
+            None
+        );
+    }
+
+    #[test]
+    fn accrued_interest_is_excluded_from_the_clean_price() {
+        // halfway through the coupon period of a 12% bond
+        let cash_flows =
+            BondCashFlows::fixed_coupon(120_000, FREQUENCY, 10, FIXED_POINT_SCALE / 2).unwrap();
+
+        assert_eq!(cash_flows.accrued_interest(), Some(bps(300)));
+
+        let dirty_price = cash_flows.dirty_price(bps(1300)).unwrap();
+        let clean_price = cash_flows.clean_price(bps(1300)).unwrap();
+        assert_eq!(dirty_price - clean_price, bps(300));
+
+        let yield_rate = cash_flows.yield_from_clean_price(clean_price).unwrap();
+        assert_close(yield_rate, bps(1300), 1_000);
+    }
+
+    #[test]
+    fn yields_outside_the_solver_range_are_rejected() {
+        let cash_flows = BondCashFlows::fixed_coupon(120_000, FREQUENCY, 20, 0).unwrap();
+
+        // above the undiscounted cash flows i.e a negative yield
+        assert_eq!(cash_flows.yield_from_clean_price(bps(40_000)), None);
+    }
+}
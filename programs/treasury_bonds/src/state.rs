@@ -1,7 +1,18 @@
 // Program state handling.
 
+pub mod amortization_schedule;
+pub mod auction;
+pub mod bid;
 pub mod bond_issuer;
 pub mod configs;
+pub mod coupon_schedule;
 pub mod deposit_base;
+pub mod holding;
 pub mod investor;
+pub mod offer;
+pub mod order;
+pub mod order_book;
+pub mod proposal;
+pub mod tax_profile;
+pub mod tax_record;
 pub mod treasury_bonds;
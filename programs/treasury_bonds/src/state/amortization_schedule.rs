@@ -0,0 +1,211 @@
+use {
+    crate::{
+        pricing::{self, BASIS_POINTS},
+        state::coupon_schedule::COUPON_INTERVAL_MONTHS,
+    },
+    anchor_lang::prelude::*,
+};
+
+// principal tranches of an amortizing bond i.e semi-annual over thirty years
+pub const MAX_PRINCIPAL_TRANCHES: usize = 60;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, InitSpace)]
+pub struct PrincipalTranche {
+    pub repayment_date: i64, // unix timestamp when the tranche falls due
+    pub repayment_rate: u16, // share of the face value repaid (basis points)
+}
+
+#[account]
+#[derive(Default, Debug, InitSpace)]
+pub struct AmortizationSchedule {
+    pub treasury_bonds: Pubkey, // treasury bonds whose principal is repaid under this schedule
+    #[max_len(60)]
+    pub tranches: Vec<PrincipalTranche>, // principal repaid before maturity, none for bullet bonds
+    pub total_principal_repaid: u64, // total principal repaid to investors (in smallest unit)
+    pub is_initialized: bool,   // is amortization schedule initiated
+}
+
+impl AmortizationSchedule {
+    // tranches should fall due in order on coupon dates between the value and redemption dates,
+    // leaving part of the face value to be repaid at maturity. coupons are paid on the face value
+    // outstanding at the start of each coupon period, so a tranche repaid mid-period would still
+    // earn the coupon of the whole period
+    pub fn is_valid_schedule(
+        tranches: &[PrincipalTranche],
+        value_date: i64,
+        redemption_date: i64,
+    ) -> bool {
+        if tranches.len() > MAX_PRINCIPAL_TRANCHES {
+            return false;
+        }
+
+        let mut last_repayment_date = value_date;
+        let mut total_repayment_rate: u64 = 0;
+        for tranche in tranches {
+            if tranche.repayment_date <= last_repayment_date
+                || tranche.repayment_date >= redemption_date
+                || tranche.repayment_rate == 0
+                || !Self::is_coupon_date(tranche.repayment_date, value_date, redemption_date)
+            {
+                return false;
+            }
+
+            last_repayment_date = tranche.repayment_date;
+            total_repayment_rate += tranche.repayment_rate as u64;
+        }
+
+        total_repayment_rate < BASIS_POINTS
+    }
+
+    // coupon dates are stepped back from the redemption date in calendar half-years
+    fn is_coupon_date(date: i64, value_date: i64, redemption_date: i64) -> bool {
+        (1..)
+            .map(|periods| pricing::add_months(redemption_date, -periods * COUPON_INTERVAL_MONTHS))
+            .take_while(|coupon_date| *coupon_date > value_date)
+            .any(|coupon_date| coupon_date == date)
+    }
+
+    // number of tranches that have fallen due as at the given unix timestamp
+    pub fn tranches_due(&self, now: i64) -> u8 {
+        self.tranches
+            .iter()
+            .take_while(|tranche| tranche.repayment_date <= now)
+            .count() as u8
+    }
+
+    // share of the face value (basis points) repaid by the given tranches
+    pub fn repayment_rate_of(&self, tranches: std::ops::Range<u8>) -> u64 {
+        self.tranches
+            .iter()
+            .skip(tranches.start as usize)
+            .take(tranches.len())
+            .map(|tranche| tranche.repayment_rate as u64)
+            .sum()
+    }
+
+    // share of the face value (basis points) outstanding once the given number of tranches is repaid
+    pub fn outstanding_rate(&self, tranches_repaid: u8) -> u64 {
+        BASIS_POINTS.saturating_sub(self.repayment_rate_of(0..tranches_repaid))
+    }
+
+    // share of the face value (basis points) outstanding as at the given unix timestamp
+    pub fn outstanding_rate_at(&self, now: i64) -> u64 {
+        self.outstanding_rate(self.tranches_due(now))
+    }
+
+    // given share (basis points) of the face value (in smallest unit)
+    pub fn share_of(face_value: u64, rate: u64) -> Option<u64> {
+        let share = (face_value as u128)
+            .checked_mul(rate as u128)?
+            .checked_div(BASIS_POINTS as u128)?;
+
+        u64::try_from(share).ok()
+    }
+
+    // principal repaid on each of the remaining coupon dates as a share (basis points)
+    // of the face value outstanding at the given unix timestamp, the balance repaid at maturity
+    pub fn remaining_repayments(&self, now: i64, coupon_dates: &[i64]) -> Option<Vec<u32>> {
+        let outstanding_rate = self.outstanding_rate_at(now);
+        let (_, coupon_dates) = coupon_dates.split_last()?;
+        if outstanding_rate == 0 {
+            return None;
+        }
+
+        let mut repayments = Vec::with_capacity(coupon_dates.len() + 1);
+        let mut total_repayment: u64 = 0;
+        let mut period_start = now;
+        for period_end in coupon_dates {
+            let repayment_rate: u64 = self
+                .tranches
+                .iter()
+                .filter(|tranche| {
+                    tranche.repayment_date > period_start && tranche.repayment_date <= *period_end
+                })
+                .map(|tranche| tranche.repayment_rate as u64)
+                .sum();
+            let repayment = repayment_rate
+                .checked_mul(BASIS_POINTS)?
+                .checked_div(outstanding_rate)?;
+
+            repayments.push(repayment as u32);
+            total_repayment = total_repayment.checked_add(repayment)?;
+            period_start = *period_end;
+        }
+
+        repayments.push(BASIS_POINTS.checked_sub(total_repayment)? as u32);
+
+        Some(repayments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pricing::{days_from_civil, SECONDS_PER_DAY};
+
+    fn date(year: i64, month: i64, day: i64) -> i64 {
+        days_from_civil(year, month, day) * SECONDS_PER_DAY
+    }
+
+    fn tranche(repayment_date: i64, repayment_rate: u16) -> PrincipalTranche {
+        PrincipalTranche {
+            repayment_date,
+            repayment_rate,
+        }
+    }
+
+    #[test]
+    fn tranches_are_repaid_on_coupon_dates() {
+        // 5 year bond from 15-05-2024 to 15-05-2029
+        let value_date = date(2024, 5, 15);
+        let redemption_date = date(2029, 5, 15);
+
+        let tranches = [
+            tranche(date(2024, 11, 15), 2500),
+            tranche(date(2027, 5, 15), 2500),
+            tranche(date(2028, 11, 15), 2500),
+        ];
+        assert!(AmortizationSchedule::is_valid_schedule(
+            &tranches,
+            value_date,
+            redemption_date
+        ));
+
+        // a tranche repaid mid-period would leave the coupon paid on the face value it repaid
+        let tranches = [tranche(date(2027, 2, 15), 5000)];
+        assert!(!AmortizationSchedule::is_valid_schedule(
+            &tranches,
+            value_date,
+            redemption_date
+        ));
+
+        // nor can a tranche be repaid on the value date
+        let tranches = [tranche(value_date, 5000)];
+        assert!(!AmortizationSchedule::is_valid_schedule(
+            &tranches,
+            value_date,
+            redemption_date
+        ));
+    }
+
+    #[test]
+    fn coupon_dates_follow_month_ends() {
+        // redemption at the end of august, coupon dates fall at the end of february
+        let value_date = date(2024, 8, 31);
+        let redemption_date = date(2027, 8, 31);
+
+        let tranches = [tranche(date(2025, 2, 28), 5000)];
+        assert!(AmortizationSchedule::is_valid_schedule(
+            &tranches,
+            value_date,
+            redemption_date
+        ));
+
+        let tranches = [tranche(date(2025, 2, 27), 5000)];
+        assert!(!AmortizationSchedule::is_valid_schedule(
+            &tranches,
+            value_date,
+            redemption_date
+        ));
+    }
+}
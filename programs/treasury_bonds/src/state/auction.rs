@@ -0,0 +1,199 @@
+use crate::{
+    error::TreasuryBondsError,
+    pricing::{
+        yield_to_maturity::{BondCashFlows, FIXED_POINT_SCALE},
+        BASIS_POINTS,
+    },
+    state::{
+        amortization_schedule::AmortizationSchedule,
+        bid::Bid,
+        coupon_schedule::{CouponSchedule, COUPON_PERIODS_PER_YEAR},
+        treasury_bonds::{TreasuryBonds, PAR_CLEAN_PRICE, ZERO_COUPON_BOND},
+    },
+};
+use anchor_lang::prelude::*;
+
+// maximum number of distinct yields that competitive bids can be submitted at
+pub const MAX_YIELD_LEVELS: usize = 32;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct YieldLevel {
+    pub yield_rate: u16, // yield bid (basis points)
+    pub amount: u32,     // total amount of competitive bids at the yield
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Auction {
+    pub treasury_bonds: Pubkey,      // treasury bonds offered in the auction
+    pub bid_opening_date: i64,       // unix timestamp when bids can first be submitted
+    pub bid_closing_date: i64,       // unix timestamp after which no bids can be submitted
+    pub total_competitive_bids: u32, // total amount of competitive bids
+    pub total_non_competitive_bids: u32, // total amount of non-competitive bids
+    #[max_len(32)]
+    pub yield_levels: Vec<YieldLevel>, // competitive bids aggregated by yield (lowest yield first)
+    pub cut_off_yield: Option<u16>,  // highest yield accepted (basis points)
+    pub clearing_price: Option<u32>, // clean price per 100 face value (2 decimals) at the cut-off yield, none when no competitive bid is accepted
+    pub marginal_allotment: u32, // amount still to be allotted to competitive bids at the cut-off yield
+    pub marginal_bids: u32,      // total amount of unsettled competitive bids at the cut-off yield
+    pub non_competitive_allotment: u32, // amount still to be allotted to non-competitive bids
+    pub is_closed: bool,         // is auction closed i.e bids allotted
+    pub is_initialized: bool,    // is auction initiated
+    pub bump: u8,                // bump of the auction PDA i.e seeded by treasury bonds
+}
+
+impl Auction {
+    pub fn is_bid_window_open(&self, now: i64) -> bool {
+        !self.is_closed && now >= self.bid_opening_date && now < self.bid_closing_date
+    }
+
+    // aggregate a competitive bid into its yield level, keeping levels sorted by yield.
+    // a bid at a new yield should be at least the minimum level amount, so that the
+    // yield levels cannot be filled up by small bids
+    pub fn add_competitive_bid(
+        &mut self,
+        yield_rate: u16,
+        amount: u32,
+        minimum_level_amount: u32,
+    ) -> Result<()> {
+        match self
+            .yield_levels
+            .binary_search_by_key(&yield_rate, |x| x.yield_rate)
+        {
+            Ok(index) => {
+                let yield_level = &mut self.yield_levels[index];
+                yield_level.amount = yield_level
+                    .amount
+                    .checked_add(amount)
+                    .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+            }
+            Err(index) => {
+                if self.yield_levels.len() >= MAX_YIELD_LEVELS {
+                    return Err(TreasuryBondsError::YieldLevelsExceeded.into());
+                }
+                if amount < minimum_level_amount {
+                    return Err(TreasuryBondsError::InvalidYieldLevelAmount.into());
+                }
+                self.yield_levels
+                    .insert(index, YieldLevel { yield_rate, amount });
+            }
+        }
+
+        self.total_competitive_bids = self
+            .total_competitive_bids
+            .checked_add(amount)
+            .ok_or(TreasuryBondsError::InvalidArithmeticOperation)?;
+
+        Ok(())
+    }
+
+    // determine the cut-off yield and pro-rata allotments for the given amount offered.
+    // non-competitive bids are allotted first, then competitive bids from the lowest yield
+    // upwards, with bids at the cut-off yield allotted pro-rata. returns the amount allotted.
+    pub fn allot(&mut self, amount_offered: u32) -> u32 {
+        if self.total_non_competitive_bids >= amount_offered {
+            self.non_competitive_allotment = amount_offered;
+            return amount_offered;
+        }
+
+        self.non_competitive_allotment = self.total_non_competitive_bids;
+        let mut remaining = amount_offered - self.total_non_competitive_bids;
+
+        for yield_level in self.yield_levels.iter() {
+            if remaining == 0 {
+                break;
+            }
+
+            let allotment = yield_level.amount.min(remaining);
+            self.cut_off_yield = Some(yield_level.yield_rate);
+            self.marginal_allotment = allotment;
+            self.marginal_bids = yield_level.amount;
+            remaining -= allotment;
+        }
+
+        amount_offered - remaining
+    }
+
+    // amount allotted to the given bid once the auction is closed. bids sharing an allotment
+    // pro-rata are allotted their share of what is still unallotted as they are settled, so
+    // that the last bid settled takes up the rounding and the allotment is used up exactly
+    pub fn allot_bid(&mut self, bid: &Bid) -> Option<u32> {
+        if !bid.is_competitive {
+            let allotment = pro_rata(
+                bid.amount,
+                self.non_competitive_allotment,
+                self.total_non_competitive_bids,
+            )?;
+            self.non_competitive_allotment =
+                self.non_competitive_allotment.checked_sub(allotment)?;
+            self.total_non_competitive_bids =
+                self.total_non_competitive_bids.checked_sub(bid.amount)?;
+
+            return Some(allotment);
+        }
+
+        match self.cut_off_yield {
+            Some(cut_off_yield) if bid.yield_rate < cut_off_yield => Some(bid.amount),
+            Some(cut_off_yield) if bid.yield_rate == cut_off_yield => {
+                let allotment = pro_rata(bid.amount, self.marginal_allotment, self.marginal_bids)?;
+                self.marginal_allotment = self.marginal_allotment.checked_sub(allotment)?;
+                self.marginal_bids = self.marginal_bids.checked_sub(bid.amount)?;
+
+                Some(allotment)
+            }
+            _ => Some(0),
+        }
+    }
+}
+
+// clean price per 100 face value (2 decimals, rounded down) of the treasury bonds at the given
+// annual yield (basis points) as at the start of the first coupon period, primary issues being
+// priced before any coupon accrues
+pub fn issue_price(
+    treasury_bonds: &TreasuryBonds,
+    coupon_schedule: &CouponSchedule,
+    amortization_schedule: &AmortizationSchedule,
+    yield_rate: u16,
+) -> Option<u32> {
+    let cash_flows = if treasury_bonds.type_of_bond == ZERO_COUPON_BOND {
+        let periods = (treasury_bonds.tenor as u64).checked_mul(COUPON_PERIODS_PER_YEAR)?;
+        BondCashFlows::zero_coupon(COUPON_PERIODS_PER_YEAR, periods as u32, 0)?
+    } else {
+        let issue_date = coupon_schedule.coupon_date(0)?;
+        let coupon_dates = (1..=coupon_schedule.total_coupon_periods)
+            .map(|period| coupon_schedule.coupon_date(period))
+            .collect::<Option<Vec<i64>>>()?;
+        let principal_repayments =
+            amortization_schedule.remaining_repayments(issue_date, &coupon_dates)?;
+
+        BondCashFlows::amortizing(
+            treasury_bonds.coupon_rate,
+            COUPON_PERIODS_PER_YEAR,
+            &principal_repayments,
+            0,
+        )?
+    };
+
+    let yield_rate = (yield_rate as u128)
+        .checked_mul(FIXED_POINT_SCALE)?
+        .checked_div(BASIS_POINTS as u128)?;
+    let clean_price = cash_flows
+        .clean_price(yield_rate)?
+        .checked_mul(PAR_CLEAN_PRICE as u128)?
+        .checked_div(FIXED_POINT_SCALE)?;
+
+    u32::try_from(clean_price).ok()
+}
+
+// amount * numerator / denominator (rounded down)
+fn pro_rata(amount: u32, numerator: u32, denominator: u32) -> Option<u32> {
+    if denominator == 0 {
+        return Some(0);
+    }
+
+    let result = (amount as u64)
+        .checked_mul(numerator as u64)?
+        .checked_div(denominator as u64)?;
+
+    u32::try_from(result).ok()
+}
@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default, Debug, InitSpace)]
+pub struct Bid {
+    pub auction: Pubkey,      // auction the bid is submitted to
+    pub investor: Pubkey,     // investor submitting the bid
+    pub owner: Pubkey,        // publickey of the investor
+    pub is_competitive: bool, // competitive bids carry a yield, non-competitive bids accept the cut-off
+    pub yield_rate: u16,      // yield bid (basis points), zero for non-competitive bids
+    pub amount: u32,          // amount of treasury bonds bid for
+    pub escrowed_amount: u64, // amount held in escrow for the bid (in smallest unit)
+    pub bump: u8,             // bump of the bid PDA i.e seeded by auction and investor
+}
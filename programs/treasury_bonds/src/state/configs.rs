@@ -1,10 +1,57 @@
 use crate::state::bond_issuer::BondIssuer;
 use anchor_lang::prelude::*;
 
+// maximum number of settlement mints in the allow-list
+pub const MAX_SETTLEMENT_MINTS: usize = 5;
+// maximum number of role grants
+pub const MAX_ROLE_GRANTS: usize = 10;
+
+// roles granted by the configs admin (super-admin)
+pub const ISSUANCE_OPERATOR: u8 = 1; // registers and manages bond issues i.e auctions, order books, maturity
+pub const TREASURER: u8 = 2; // funds the treasury vault
+pub const COMPLIANCE_OFFICER: u8 = 3; // manages investors i.e activates and deactivates them
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, InitSpace)]
+pub struct RoleGrant {
+    pub role: u8,        // role granted i.e issuance operator, treasurer, compliance officer
+    pub account: Pubkey, // publickey the role is granted to
+}
+
 #[account]
 #[derive(Default, InitSpace)]
 pub struct TreasuryBondsConfigs {
     #[max_len(5)]
     pub issuers: Vec<BondIssuer>,
     pub is_initialized: bool,
+    pub admin: Pubkey, // publickey of the configs admin (super-admin) i.e manages the settlement mints and roles
+    pub pending_admin: Option<Pubkey>, // admin proposed by the configs admin, takes over once it accepts
+    #[max_len(5)]
+    pub settlement_mints: Vec<Pubkey>, // mints accepted for settlement i.e USDC or a configured stablecoin
+    #[max_len(10)]
+    pub roles: Vec<RoleGrant>, // roles granted by the configs admin
+    #[max_len(10)]
+    pub approvers: Vec<Pubkey>, // approvers of proposals for sensitive admin actions
+    pub approval_threshold: u8, // approvals required to execute a proposal i.e M of N approvers
+    pub proposal_timeout: i64,  // seconds a proposal remains open for approval and execution
+    pub next_proposal_id: u64,  // id of the next proposal
+}
+
+impl TreasuryBondsConfigs {
+    pub fn is_settlement_mint(&self, mint: &Pubkey) -> bool {
+        self.settlement_mints.contains(mint)
+    }
+
+    pub fn is_valid_role(role: u8) -> bool {
+        matches!(role, ISSUANCE_OPERATOR | TREASURER | COMPLIANCE_OFFICER)
+    }
+
+    pub fn is_approver(&self, account: &Pubkey) -> bool {
+        self.approvers.contains(account)
+    }
+
+    pub fn has_role(&self, role: u8, account: &Pubkey) -> bool {
+        self.roles
+            .iter()
+            .any(|grant| grant.role == role && grant.account == *account)
+    }
 }
@@ -0,0 +1,199 @@
+use {crate::pricing, anchor_lang::prelude::*};
+
+// coupons are paid semi-annually i.e two coupon periods per year
+pub const COUPON_PERIODS_PER_YEAR: u64 = 2;
+// calendar months between coupon dates
+pub const COUPON_INTERVAL_MONTHS: i64 = 6;
+
+#[account]
+#[derive(Default, Debug, InitSpace)]
+pub struct CouponSchedule {
+    pub treasury_bonds: Pubkey, // treasury bonds whose coupons are paid under this schedule
+    pub maturity_date: i64,     // unix timestamp of the final coupon date i.e the redemption date
+    pub total_coupon_periods: u8, // number of coupon periods i.e tenor * 2
+    pub total_coupons_paid: u64, // total coupons paid out to investors (in smallest unit)
+    pub is_initialized: bool,   // is coupon schedule initiated
+}
+
+impl CouponSchedule {
+    // unix timestamp when the given coupon period (1st, 2nd, ...) falls due. coupon dates are
+    // stepped back from the maturity date in calendar half-years so that the final coupon is
+    // paid on the redemption date, period 0 being the start of the first coupon period.
+    pub fn coupon_date(&self, period: u8) -> Option<i64> {
+        let periods_to_maturity = self.total_coupon_periods.checked_sub(period)? as i64;
+
+        Some(pricing::add_months(
+            self.maturity_date,
+            -periods_to_maturity * COUPON_INTERVAL_MONTHS,
+        ))
+    }
+
+    // number of coupon periods that have fallen due as at the given unix timestamp
+    pub fn periods_due(&self, now: i64) -> u8 {
+        (1..=self.total_coupon_periods)
+            .take_while(|period| {
+                self.coupon_date(*period)
+                    .is_some_and(|coupon_date| coupon_date <= now)
+            })
+            .count() as u8
+    }
+
+    // coupon accrued on the given face value (in smallest unit) since the last coupon date,
+    // nothing accrues once the final coupon has fallen due
+    pub fn accrued_coupon(
+        &self,
+        face_value: u64,
+        coupon_rate: u32,
+        day_count: u8,
+        now: i64,
+    ) -> Option<u64> {
+        let periods_due = self.periods_due(now);
+        if periods_due >= self.total_coupon_periods {
+            return Some(0);
+        }
+
+        let period_start = self.coupon_date(periods_due)?;
+        let period_end = self.coupon_date(periods_due + 1)?;
+
+        pricing::accrued_interest(
+            face_value,
+            coupon_rate,
+            day_count,
+            period_start,
+            period_end,
+            now,
+            COUPON_PERIODS_PER_YEAR,
+        )
+    }
+
+    // coupon paid on the given face value (in smallest unit) for the given coupon period (1st, 2nd, ...)
+    pub fn coupon_of_period(
+        &self,
+        face_value: u64,
+        coupon_rate: u32,
+        day_count: u8,
+        period: u8,
+    ) -> Option<u64> {
+        let period_start = self.coupon_date(period.checked_sub(1)?)?;
+        let period_end = self.coupon_date(period)?;
+
+        pricing::coupon_payment(
+            face_value,
+            coupon_rate,
+            day_count,
+            period_start,
+            period_end,
+            COUPON_PERIODS_PER_YEAR,
+        )
+    }
+
+    // most coupon that can accrue on the given face value (in smallest unit) from the given
+    // unix timestamp to maturity i.e the largest coupon of the periods yet to fall due
+    pub fn max_accrued_coupon(
+        &self,
+        face_value: u64,
+        coupon_rate: u32,
+        day_count: u8,
+        now: i64,
+    ) -> Option<u64> {
+        let mut max_coupon = 0;
+        for period in self.periods_due(now) + 1..=self.total_coupon_periods {
+            max_coupon = max_coupon.max(self.coupon_of_period(
+                face_value,
+                coupon_rate,
+                day_count,
+                period,
+            )?);
+        }
+
+        Some(max_coupon)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pricing::{days_from_civil, ACTUAL_365, ACTUAL_ACTUAL_ICMA, SECONDS_PER_DAY};
+
+    // 12.9655% coupon on 1,000,000 face value
+    const COUPON_RATE: u32 = 129_655;
+    const FACE_VALUE: u64 = 1_000_000;
+
+    fn date(year: i64, month: i64, day: i64) -> i64 {
+        days_from_civil(year, month, day) * SECONDS_PER_DAY
+    }
+
+    // 5 year bond from 15-05-2024 to 15-05-2029 i.e 10 half-yearly coupon periods
+    fn coupon_schedule() -> CouponSchedule {
+        CouponSchedule {
+            maturity_date: date(2029, 5, 15),
+            total_coupon_periods: 10,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn coupons_paid_per_period() {
+        let coupon_schedule = coupon_schedule();
+
+        // 184 day period from 15-05-2024 to 15-11-2024
+        // actual/365: 1,000,000 * 12.9655% * 184 / 365 = 65,360.33
+        let coupon = coupon_schedule.coupon_of_period(FACE_VALUE, COUPON_RATE, ACTUAL_365, 1);
+        assert_eq!(coupon, Some(65_360));
+
+        // actual/actual (ICMA): 1,000,000 * 12.9655% / 2 = 64,827.50
+        let coupon =
+            coupon_schedule.coupon_of_period(FACE_VALUE, COUPON_RATE, ACTUAL_ACTUAL_ICMA, 1);
+        assert_eq!(coupon, Some(64_827));
+
+        // 181 day period from 15-11-2024 to 15-05-2025
+        // actual/365: 1,000,000 * 12.9655% * 181 / 365 = 64,294.67
+        let coupon = coupon_schedule.coupon_of_period(FACE_VALUE, COUPON_RATE, ACTUAL_365, 2);
+        assert_eq!(coupon, Some(64_294));
+
+        let coupon =
+            coupon_schedule.coupon_of_period(FACE_VALUE, COUPON_RATE, ACTUAL_ACTUAL_ICMA, 2);
+        assert_eq!(coupon, Some(64_827));
+    }
+
+    #[test]
+    fn coupon_accrued_on_a_sale() {
+        let coupon_schedule = coupon_schedule();
+        let now = date(2024, 8, 15);
+
+        // 92 days into the 184 day period from 15-05-2024
+        // actual/365: 1,000,000 * 12.9655% * 92 / 365 = 32,680.16
+        let accrued = coupon_schedule.accrued_coupon(FACE_VALUE, COUPON_RATE, ACTUAL_365, now);
+        assert_eq!(accrued, Some(32_680));
+
+        // actual/actual (ICMA): 1,000,000 * 12.9655% * 92 / (2 * 184) = 32,413.75
+        let accrued =
+            coupon_schedule.accrued_coupon(FACE_VALUE, COUPON_RATE, ACTUAL_ACTUAL_ICMA, now);
+        assert_eq!(accrued, Some(32_413));
+    }
+
+    #[test]
+    fn coupon_accrued_on_redemption() {
+        let coupon_schedule = coupon_schedule();
+
+        // early redemption 106 days into the 181 day period from 15-11-2026
+        // actual/365: 1,000,000 * 12.9655% * 106 / 365 = 37,653.23
+        let accrued =
+            coupon_schedule.accrued_coupon(FACE_VALUE, COUPON_RATE, ACTUAL_365, date(2027, 3, 1));
+        assert_eq!(accrued, Some(37_653));
+
+        // actual/actual (ICMA): 1,000,000 * 12.9655% * 106 / (2 * 181) = 37,965.28
+        let accrued = coupon_schedule.accrued_coupon(
+            FACE_VALUE,
+            COUPON_RATE,
+            ACTUAL_ACTUAL_ICMA,
+            date(2027, 3, 1),
+        );
+        assert_eq!(accrued, Some(37_965));
+
+        // the final coupon is paid with the redemption, nothing accrues at maturity
+        let accrued =
+            coupon_schedule.accrued_coupon(FACE_VALUE, COUPON_RATE, ACTUAL_365, date(2029, 5, 15));
+        assert_eq!(accrued, Some(0));
+    }
+}
@@ -0,0 +1,51 @@
+use {crate::error::TreasuryBondsError, anchor_lang::prelude::*};
+
+#[account]
+#[derive(Default, Debug, InitSpace)]
+pub struct Holding {
+    pub investor: Pubkey,       // investor account holding the treasury bonds
+    pub treasury_bonds: Pubkey, // treasury bonds held by the investor
+    pub total_units_treasury_bonds: u32, // total units of treasury bonds owned by investor
+    pub available_funds: u32,   // available funds equal to the treasury bonds owned by investor
+    pub cost_basis: u64,        // total amount paid for the treasury bonds held (in smallest unit)
+    pub coupon_periods_paid: u8, // number of coupon periods already paid to investor
+    pub total_coupons_received: u64, // total coupons paid to investor (in smallest unit)
+    pub principal_tranches_repaid: u8, // number of principal tranches already repaid to investor
+    pub total_principal_received: u64, // total principal repaid to investor before maturity (in smallest unit)
+    pub is_initialized: bool,          // is holding initiated
+    pub bump: u8, // bump of the holding PDA i.e seeded by investor and treasury bonds
+}
+
+impl Holding {
+    // cost basis attributable to the given amount of the treasury bonds held
+    pub fn cost_basis_of(&self, amount: u32) -> Option<u64> {
+        if self.available_funds == 0 {
+            return Some(0);
+        }
+
+        let cost_basis = (self.cost_basis as u128)
+            .checked_mul(amount as u128)?
+            .checked_div(self.available_funds as u128)?;
+
+        u64::try_from(cost_basis).ok()
+    }
+
+    // coupons and principal that have fallen due should be paid to the holder before the
+    // treasury bonds held change hands, otherwise they would be earned on the units
+    // bought or lost on the units sold. holdings with no treasury bonds have nothing due
+    pub fn verify_settled(&self, periods_due: u8, tranches_due: u8) -> Result<()> {
+        if self.available_funds == 0 {
+            return Ok(());
+        }
+
+        if self.coupon_periods_paid < periods_due {
+            return Err(TreasuryBondsError::CouponPaymentDue.into());
+        }
+
+        if self.principal_tranches_repaid < tranches_due {
+            return Err(TreasuryBondsError::PrincipalRepaymentDue.into());
+        }
+
+        Ok(())
+    }
+}
@@ -9,6 +9,18 @@ pub struct Investor {
     #[max_len(3)]
     pub country: String, // home country of investor
     pub active: bool,  // status of investor
+}
+
+// layout of investor accounts registered while investor positions were held on the
+// investor account itself, kept only for migrating those accounts
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct LegacyInvestor {
+    pub owner: Pubkey,
+    #[max_len(50)]
+    pub full_names: String,
+    #[max_len(3)]
+    pub country: String,
+    pub active: bool,
     pub total_units_treasury_bonds: u32, // total units of treasury bonds owned by investor
     pub available_funds: u32, // available funds equal to the treasury bonds owned by investor
 }
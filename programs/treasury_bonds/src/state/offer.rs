@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default, Debug, InitSpace)]
+pub struct Offer {
+    pub treasury_bonds: Pubkey,  // treasury bonds offered for sale
+    pub seller_investor: Pubkey, // investor selling the treasury bonds
+    pub owner: Pubkey,           // publickey of the seller i.e signed the offer
+    pub amount: u32,             // amount of treasury bonds still offered
+    pub clean_price: u32,        // clean price per 100 face value (2 decimals i.e 9850 = 98.50)
+    pub expiry_date: i64,        // offer can no longer be accepted after this date (unix timestamp)
+    pub bump: u8, // bump of the offer PDA i.e seeded by treasury bonds and seller investor
+}
+
+impl Offer {
+    pub fn has_expired(&self, now: i64) -> bool {
+        now >= self.expiry_date
+    }
+}
@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default, Debug, InitSpace)]
+pub struct Order {
+    pub order_book: Pubkey,   // order book the order rests on
+    pub investor: Pubkey,     // investor placing the order
+    pub owner: Pubkey,        // publickey of the investor
+    pub order_id: u64,        // id of the order i.e sequence in which orders were placed
+    pub side: u8,             // bid (buy) or ask (sell) order
+    pub clean_price: u32,     // clean price per 100 face value (2 decimals i.e 9850 = 98.50)
+    pub amount: u32,          // amount of treasury bonds still to be bought or sold
+    pub escrowed_amount: u64, // amount held in escrow for a bid (in smallest unit)
+    pub bump: u8,             // bump of the order PDA i.e seeded by order book and order id
+}
@@ -0,0 +1,157 @@
+use {crate::error::TreasuryBondsError, anchor_lang::prelude::*};
+
+// maximum number of resting orders on each side of the order book,
+// bounds the work of placing, cancelling and matching orders
+pub const MAX_ORDERS_PER_SIDE: usize = 32;
+
+// sides of an order
+pub const BID_ORDER: u8 = 1;
+pub const ASK_ORDER: u8 = 2;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct OrderEntry {
+    pub order_id: u64, // id of the resting order i.e orders placed earlier have lower ids
+    pub clean_price: u32, // clean price per 100 face value (2 decimals i.e 9850 = 98.50)
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct OrderBook {
+    pub treasury_bonds: Pubkey, // treasury bonds traded on the order book
+    pub next_order_id: u64,     // id assigned to the next order placed
+    #[max_len(32)]
+    pub bids: Vec<OrderEntry>, // resting bids (highest price first, then earliest order)
+    #[max_len(32)]
+    pub asks: Vec<OrderEntry>, // resting asks (lowest price first, then earliest order)
+    pub is_initialized: bool,   // is order book initiated
+    pub bump: u8,               // bump of the order book PDA i.e seeded by treasury bonds
+}
+
+impl OrderBook {
+    // add a resting order behind all orders at the same or a better price i.e price-time priority.
+    // on a full side, an order priced better than the worst resting order takes its place and
+    // the id of the evicted order is returned, its escrow is reclaimed by cancelling the order
+    pub fn add_order(&mut self, side: u8, order_id: u64, clean_price: u32) -> Result<Option<u64>> {
+        let orders = self
+            .orders_mut(side)
+            .ok_or(TreasuryBondsError::InvalidOrderSide)?;
+
+        let is_better = |entry: &OrderEntry| {
+            if side == BID_ORDER {
+                entry.clean_price < clean_price
+            } else {
+                entry.clean_price > clean_price
+            }
+        };
+
+        let mut evicted_order_id = None;
+        if orders.len() >= MAX_ORDERS_PER_SIDE {
+            match orders.last() {
+                Some(worst) if is_better(worst) => {
+                    evicted_order_id = orders.pop().map(|entry| entry.order_id);
+                }
+                _ => return Err(TreasuryBondsError::OrderBookFull.into()),
+            }
+        }
+
+        let position = orders.iter().position(is_better).unwrap_or(orders.len());
+
+        orders.insert(
+            position,
+            OrderEntry {
+                order_id,
+                clean_price,
+            },
+        );
+
+        Ok(evicted_order_id)
+    }
+
+    // remove a resting order once it is filled or cancelled
+    pub fn remove_order(&mut self, side: u8, order_id: u64) -> Option<()> {
+        let orders = self.orders_mut(side)?;
+        let position = orders.iter().position(|entry| entry.order_id == order_id)?;
+        orders.remove(position);
+
+        Some(())
+    }
+
+    // order with the best price on the given side, earliest first at the same price
+    pub fn best_order(&self, side: u8) -> Option<&OrderEntry> {
+        match side {
+            BID_ORDER => self.bids.first(),
+            ASK_ORDER => self.asks.first(),
+            _ => None,
+        }
+    }
+
+    fn orders_mut(&mut self, side: u8) -> Option<&mut Vec<OrderEntry>> {
+        match side {
+            BID_ORDER => Some(&mut self.bids),
+            ASK_ORDER => Some(&mut self.asks),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order_book() -> OrderBook {
+        OrderBook {
+            treasury_bonds: Pubkey::default(),
+            next_order_id: 0,
+            bids: Vec::new(),
+            asks: Vec::new(),
+            is_initialized: true,
+            bump: 0,
+        }
+    }
+
+    fn order_ids(orders: &[OrderEntry]) -> Vec<u64> {
+        orders.iter().map(|entry| entry.order_id).collect()
+    }
+
+    #[test]
+    fn orders_rest_in_price_time_priority() {
+        let mut order_book = order_book();
+
+        order_book.add_order(BID_ORDER, 0, 9800).unwrap();
+        order_book.add_order(BID_ORDER, 1, 9900).unwrap();
+        order_book.add_order(BID_ORDER, 2, 9800).unwrap();
+        order_book.add_order(ASK_ORDER, 3, 10100).unwrap();
+        order_book.add_order(ASK_ORDER, 4, 10000).unwrap();
+
+        assert_eq!(order_ids(&order_book.bids), vec![1, 0, 2]);
+        assert_eq!(order_ids(&order_book.asks), vec![4, 3]);
+    }
+
+    #[test]
+    fn better_priced_order_evicts_the_worst_order_of_a_full_side() {
+        let mut order_book = order_book();
+
+        // a full side of bids at 90.00 - 93.10, the worst bid last
+        for order_id in 0..MAX_ORDERS_PER_SIDE as u64 {
+            let evicted_order_id = order_book
+                .add_order(BID_ORDER, order_id, 9310 - order_id as u32 * 10)
+                .unwrap();
+            assert_eq!(evicted_order_id, None);
+        }
+
+        // bids not priced better than the worst bid are rejected
+        assert!(order_book.add_order(BID_ORDER, 32, 9000).is_err());
+        assert!(order_book.add_order(BID_ORDER, 33, 8900).is_err());
+        assert_eq!(order_book.bids.len(), MAX_ORDERS_PER_SIDE);
+
+        // a better priced bid takes the place of the worst bid
+        let evicted_order_id = order_book.add_order(BID_ORDER, 34, 9500).unwrap();
+        assert_eq!(evicted_order_id, Some(31));
+        assert_eq!(order_book.bids.len(), MAX_ORDERS_PER_SIDE);
+        assert_eq!(order_book.best_order(BID_ORDER).unwrap().order_id, 34);
+        assert!(order_book.remove_order(BID_ORDER, 31).is_none());
+
+        // the other side of the book is unaffected
+        assert_eq!(order_book.add_order(ASK_ORDER, 35, 9000).unwrap(), None);
+    }
+}
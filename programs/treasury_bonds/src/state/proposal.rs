@@ -0,0 +1,73 @@
+use {
+    crate::state::configs::TreasuryBondsConfigs,
+    anchor_lang::{prelude::*, solana_program::hash},
+};
+
+// maximum number of approvers of proposals
+pub const MAX_APPROVERS: usize = 10;
+// minimum approvals required to execute a proposal i.e no single approver acts alone
+pub const MIN_APPROVAL_THRESHOLD: u8 = 2;
+
+// admin actions executed through an approved proposal
+pub const REGISTER_TREASURY_BONDS: u8 = 1;
+pub const DECLARE_MATURITY: u8 = 2;
+pub const WITHDRAW_VAULT: u8 = 3;
+pub const SET_APPROVERS: u8 = 4;
+pub const UPDATE_TAX_PROFILE: u8 = 5;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Proposal {
+    pub proposal_id: u64,       // sequence in which the proposal was created
+    pub proposer: Pubkey,       // approver who created the proposal
+    pub action: u8, // admin action i.e register treasury bonds, declare maturity, withdraw vault, set approvers, update tax profile
+    pub target: Pubkey, // account the action applies to i.e the treasury bonds
+    pub payload_hash: [u8; 32], // sha256 hash of the serialized instruction params of the action
+    #[max_len(10)]
+    pub approvals: Vec<Pubkey>, // approvers who signed off on the proposal
+    pub created_at: i64, // unix timestamp when the proposal was created
+    pub expires_at: i64, // unix timestamp after which the proposal can no longer be approved or executed
+    pub is_executed: bool, // is the action executed i.e the proposal can not be used again
+    pub bump: u8,        // bump of the proposal PDA i.e seeded by proposal id
+}
+
+impl Proposal {
+    pub fn is_valid_action(action: u8) -> bool {
+        matches!(
+            action,
+            REGISTER_TREASURY_BONDS
+                | DECLARE_MATURITY
+                | WITHDRAW_VAULT
+                | SET_APPROVERS
+                | UPDATE_TAX_PROFILE
+        )
+    }
+
+    // hash of the instruction params an action is proposed with
+    pub fn payload_hash_of(payload: &[u8]) -> [u8; 32] {
+        hash::hash(payload).to_bytes()
+    }
+
+    pub fn has_expired(&self, now: i64) -> bool {
+        now > self.expires_at
+    }
+
+    // approvals of accounts that are still approvers meet the configured threshold
+    pub fn is_approved(&self, treasury_bonds_configs: &TreasuryBondsConfigs) -> bool {
+        let approvals = self
+            .approvals
+            .iter()
+            .filter(|approver| treasury_bonds_configs.approvers.contains(approver))
+            .count();
+
+        treasury_bonds_configs.approval_threshold > 0
+            && approvals >= treasury_bonds_configs.approval_threshold as usize
+    }
+
+    // proposal authorizes the given action on the target with the given params
+    pub fn authorizes(&self, action: u8, target: &Pubkey, payload: &[u8]) -> bool {
+        self.action == action
+            && self.target == *target
+            && self.payload_hash == Self::payload_hash_of(payload)
+    }
+}
@@ -0,0 +1,84 @@
+use {
+    crate::{
+        error::TreasuryBondsError,
+        pricing::BASIS_POINTS,
+        state::treasury_bonds::{TreasuryBonds, INFRASTRUCTURE_BOND},
+    },
+    anchor_lang::prelude::*,
+};
+
+// withholding tax on coupons of fixed coupon bonds (basis points), depending on tenor
+pub const MINIMUM_WITHHOLDING_TAX_RATE: u16 = 1000;
+pub const MAXIMUM_WITHHOLDING_TAX_RATE: u16 = 1500;
+// tenor (years) from which the lower withholding tax rate applies
+pub const LONG_TENOR: u8 = 10;
+
+#[account]
+#[derive(Default, Debug, InitSpace)]
+pub struct TaxProfile {
+    pub treasury_bonds: Pubkey, // treasury bonds whose coupons are taxed under this profile
+    pub is_tax_exempt: bool,    // coupons are paid without withholding tax i.e infrastructure bonds
+    pub withholding_tax_rate: u16, // withholding tax on coupons (basis points)
+    pub tax_authority_tokens: Pubkey, // token account receiving the withheld tax
+    pub is_initialized: bool,   // is tax profile initiated
+    pub bump: u8,               // bump of the tax profile PDA i.e seeded by treasury bonds
+}
+
+impl TaxProfile {
+    // withholding tax on coupons of fixed coupon bonds i.e 15% below ten years, 10% from ten years
+    pub fn default_withholding_tax_rate(tenor: u8) -> u16 {
+        if tenor >= LONG_TENOR {
+            MINIMUM_WITHHOLDING_TAX_RATE
+        } else {
+            MAXIMUM_WITHHOLDING_TAX_RATE
+        }
+    }
+
+    // applies the withholding tax rate and tax authority of the treasury bonds' coupons.
+    // infrastructure bonds are exempt from withholding tax
+    pub fn apply(
+        &mut self,
+        treasury_bonds: &TreasuryBonds,
+        withholding_tax_rate: Option<u16>,
+        tax_authority_tokens: Pubkey,
+    ) -> Result<()> {
+        let is_tax_exempt = treasury_bonds.type_of_bond == INFRASTRUCTURE_BOND;
+
+        let withholding_tax_rate = if is_tax_exempt {
+            match withholding_tax_rate {
+                None | Some(0) => 0,
+                Some(_) => return Err(TreasuryBondsError::InvalidWithholdingTaxRate.into()),
+            }
+        } else {
+            let withholding_tax_rate = withholding_tax_rate
+                .unwrap_or(Self::default_withholding_tax_rate(treasury_bonds.tenor));
+
+            if !(MINIMUM_WITHHOLDING_TAX_RATE..=MAXIMUM_WITHHOLDING_TAX_RATE)
+                .contains(&withholding_tax_rate)
+            {
+                return Err(TreasuryBondsError::InvalidWithholdingTaxRate.into());
+            }
+
+            withholding_tax_rate
+        };
+
+        self.is_tax_exempt = is_tax_exempt;
+        self.withholding_tax_rate = withholding_tax_rate;
+        self.tax_authority_tokens = tax_authority_tokens;
+
+        Ok(())
+    }
+
+    // tax withheld from the given coupon (in smallest unit)
+    pub fn withholding_tax_of(&self, coupon: u64) -> Option<u64> {
+        if self.is_tax_exempt {
+            return Some(0);
+        }
+
+        let tax = (coupon as u128)
+            .checked_mul(self.withholding_tax_rate as u128)?
+            .checked_div(BASIS_POINTS as u128)?;
+
+        u64::try_from(tax).ok()
+    }
+}
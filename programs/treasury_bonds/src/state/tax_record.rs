@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default, Debug, InitSpace)]
+pub struct TaxRecord {
+    pub investor: Pubkey,        // investor the coupons were paid to
+    pub tax_year: u16,           // calendar year the coupons were paid in
+    pub total_coupons: u64,      // total coupons before withholding tax (in smallest unit)
+    pub total_tax_withheld: u64, // total withholding tax deducted from coupons (in smallest unit)
+    pub is_initialized: bool,    // is tax record initiated
+    pub bump: u8,                // bump of the tax record PDA i.e seeded by investor
+}
@@ -1,30 +1,302 @@
-use crate::state::bond_issuer::BondIssuer;
+use crate::{
+    pricing::{
+        self,
+        yield_to_maturity::{BondCashFlows, FIXED_POINT_SCALE},
+        BASIS_POINTS,
+    },
+    state::{
+        bond_issuer::BondIssuer,
+        coupon_schedule::{COUPON_INTERVAL_MONTHS, COUPON_PERIODS_PER_YEAR},
+    },
+};
 use anchor_lang::prelude::*;
 
+// types of bond
+pub const FIXED_COUPON_BOND: u8 = 1;
+pub const INFRASTRUCTURE_BOND: u8 = 2;
+pub const ZERO_COUPON_BOND: u8 = 3;
+
+// average length of a year (365.25 days) in seconds
+pub const SECONDS_PER_YEAR: i64 = 31_557_600;
+// allowance (7 days) for redemption dates adjusted to the next business day
+pub const REDEMPTION_DATE_TOLERANCE: i64 = 7 * 24 * 60 * 60;
+// clean price of par i.e 100.00 per 100 face value (2 decimals)
+pub const PAR_CLEAN_PRICE: u32 = 10_000;
+// yield curve of zero coupon bonds i.e a base yield plus a term premium for each year of tenor (basis points)
+pub const ZERO_COUPON_BASE_YIELD: u16 = 1000;
+pub const ZERO_COUPON_TERM_PREMIUM: u16 = 25;
+
 #[account]
-#[derive(InitSpace)]
+#[derive(Default, InitSpace)]
 pub struct TreasuryBonds {
-    pub owner: Pubkey, // publickey of the treasury bonds admin
+    pub owner: Pubkey, // publickey of the issuance operator who registered the treasury bonds
     pub issuer: BondIssuer,
     #[max_len(3)]
     pub country: String, // home country where treasury bonds is auctioned
     #[max_len(20)]
     pub issue_no: String, // issue no of bond
-    pub type_of_bond: u8, // type of bond i.e Fixed coupon Treasury bonds, Infrastructure bonds
+    pub type_of_bond: u8, // type of bond i.e Fixed coupon Treasury bonds, Infrastructure bonds, Zero coupon bonds
     pub tenor: u8,        // maturity period i.e between 2-30 years
-    pub coupon_rate: u8,  // coupon rate (%)
+    pub coupon_rate: u32, // coupon rate in ten-thousandths of a percent i.e 129_655 = 12.9655%
+    pub discount_rate: u16, // annual yield (basis points) used to discount zero coupon bonds from face value, derived from the tenor
     pub total_amounts_offered: u32, // total amounts offered for the given bond
     pub total_amounts_accepted: u32, // total amounts accepted from bondholders (investors)
     pub total_available_funds: u32, // total available funds equal to the treasury bonds owned by investors
     pub minimum_bid_amount: u32,    // minimum bid amount
     pub is_initialized: bool,       // is treasury bonds initiated
-    #[max_len(10)]
-    pub investors: Vec<Pubkey>, // list of the investors
+    pub holders_count: u32, // number of investors holding the treasury bonds i.e holdings with available funds
     pub unit_cost_of_treasury_bonds: u32, // unit cost of treasury bonds
-    pub decimals: u8,               // decimals for the token mint
+    pub decimals: u8,       // decimals for the token mint
+    pub value_date: i64,    // value date of bond (unix timestamp)
+    pub redemption_date: i64, // redemption date of bond (unix timestamp)
+    pub is_matured: bool,   // is treasury bonds matured early i.e called by admin
+    pub deposit_account: Pubkey, // deposit account holding the bumps of the treasury vault
+    pub settlement_mint: Pubkey, // mint the treasury bonds are bought, sold and redeemed in i.e USDC
+    pub minimum_trade_amount: u32, // minimum amount of a partial sell or redemption, defaults to the minimum bid amount
+    pub allows_early_redemption: bool, // can investors redeem the treasury bonds before maturity
+    pub day_count: u8, // day-count convention of coupons and accrued interest i.e Actual/365, Actual/Actual (ICMA), 30/360
+    pub bond_mint: Pubkey, // mint of the bond units held by investors i.e one token per unit of treasury bonds
+    pub vault_tokens: Pubkey, // token account of the treasury vault i.e receives purchase proceeds and pays out redemptions
+    pub bump: u8,             // bump of the treasury bonds PDA i.e seeded by admin and issue no
+}
+
+impl TreasuryBonds {
+    // value date should precede the redemption date, which falls tenor years after the value date
+    pub fn is_valid_term(value_date: i64, redemption_date: i64, tenor: u8) -> bool {
+        if value_date <= 0 || redemption_date <= value_date {
+            return false;
+        }
+
+        let expected_redemption_date = (tenor as i64)
+            .checked_mul(SECONDS_PER_YEAR)
+            .and_then(|term| value_date.checked_add(term));
+
+        match expected_redemption_date {
+            Some(expected_redemption_date) => {
+                (redemption_date - expected_redemption_date).abs() <= REDEMPTION_DATE_TOLERANCE
+            }
+            None => false,
+        }
+    }
+
+    // treasury bonds mature once the redemption date passes or when called early by admin
+    pub fn has_matured(&self, now: i64) -> bool {
+        self.is_matured || now >= self.redemption_date
+    }
+
+    // a trade (sell or redemption) is either the investor's whole position or a partial
+    // amount of at least the minimum trade amount that leaves at least that amount held
+    pub fn is_valid_trade_amount(&self, amount: u32, available_funds: u32) -> bool {
+        if amount == 0 || amount > available_funds {
+            return false;
+        }
+
+        if amount == available_funds {
+            return true;
+        }
+
+        amount >= self.minimum_trade_amount && available_funds - amount >= self.minimum_trade_amount
+    }
+
+    // annual yield (basis points) of zero coupon bonds of the given tenor,
+    // longer tenors are discounted at a higher yield
+    pub fn zero_coupon_discount_rate(tenor: u8) -> u16 {
+        ZERO_COUPON_BASE_YIELD + ZERO_COUPON_TERM_PREMIUM * tenor as u16
+    }
+
+    // cash flows of zero coupon bonds as at the given unix timestamp, which have no coupon
+    // schedule. the face value is discounted over half-years stepped back from the redemption date
+    pub fn zero_coupon_cash_flows(&self, now: i64) -> Option<BondCashFlows> {
+        let (periods, accrued_fraction) = pricing::discount_periods(
+            self.value_date,
+            self.redemption_date,
+            now,
+            COUPON_INTERVAL_MONTHS,
+        )?;
+
+        BondCashFlows::zero_coupon(COUPON_PERIODS_PER_YEAR, periods, accrued_fraction)
+    }
+
+    // price payable for the given face value (in smallest unit) of the bond as at the given
+    // unix timestamp. zero coupon bonds are bought at a discount i.e face value discounted at
+    // the discount rate over the time left to redemption, accreting to par at maturity, the
+    // same as their price quote. all other bonds are bought at par.
+    pub fn purchase_price(&self, face_value: u64, now: i64) -> Option<u64> {
+        if self.type_of_bond != ZERO_COUPON_BOND {
+            return Some(face_value);
+        }
+
+        let discount_rate = (self.discount_rate as u128)
+            .checked_mul(FIXED_POINT_SCALE)?
+            .checked_div(BASIS_POINTS as u128)?;
+        let price = self
+            .zero_coupon_cash_flows(now)?
+            .dirty_price(discount_rate)?;
+
+        let price = (face_value as u128)
+            .checked_mul(price)?
+            .checked_div(FIXED_POINT_SCALE)?;
+
+        u64::try_from(price).ok()
+    }
+
+    // face value (in smallest unit) of the given amount of the bond i.e amount * 10 ** decimals
+    pub fn face_value_of(&self, amount: u32) -> Option<u64> {
+        (amount as u64).checked_mul(10u64.checked_pow(self.decimals as u32)?)
+    }
+
+    // amount payable (in smallest unit) for the given amount of the bond at a clean price
+    // i.e amount * clean price / 100, clean price is quoted per 100 face value (2 decimals)
+    pub fn clean_value_of(&self, amount: u32, clean_price: u32) -> Option<u64> {
+        let value = (self.face_value_of(amount)? as u128)
+            .checked_mul(clean_price as u128)?
+            .checked_div(PAR_CLEAN_PRICE as u128)?;
+
+        u64::try_from(value).ok()
+    }
+}
+
+// layout of treasury bonds accounts registered before value and redemption dates
+// were stored as unix timestamps and before the PDA was seeded by issue no,
+// kept only for migrating those accounts
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct LegacyTreasuryBonds {
+    pub owner: Pubkey,
+    pub issuer: BondIssuer,
+    #[max_len(3)]
+    pub country: String,
+    #[max_len(20)]
+    pub issue_no: String,
+    pub type_of_bond: u8,
+    pub tenor: u8,
+    pub coupon_rate: u8,
+    pub total_amounts_offered: u32,
+    pub total_amounts_accepted: u32,
+    pub total_available_funds: u32,
+    pub minimum_bid_amount: u32,
+    pub is_initialized: bool,
+    #[max_len(10)]
+    pub investors: Vec<Pubkey>,
+    pub unit_cost_of_treasury_bonds: u32,
+    pub decimals: u8,
     #[max_len(20)]
-    pub value_date: String, // value date of bond
+    pub value_date: String, // value date of bond i.e DD-MM-YYYY
     #[max_len(20)]
-    pub redemption_date: String, // redemption date of bond
-    pub is_matured: bool,           // is treasury bonds matured
+    pub redemption_date: String, // redemption date of bond i.e DD-MM-YYYY
+    pub is_matured: bool,
+}
+
+// layout of treasury bonds accounts registered while coupon rates were whole percentages,
+// kept only for migrating those accounts
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct PercentCouponTreasuryBonds {
+    pub owner: Pubkey,
+    pub issuer: BondIssuer,
+    #[max_len(3)]
+    pub country: String,
+    #[max_len(20)]
+    pub issue_no: String,
+    pub type_of_bond: u8,
+    pub tenor: u8,
+    pub coupon_rate: u8,   // coupon rate (%)
+    pub discount_rate: u8, // discount rate (%)
+    pub total_amounts_offered: u32,
+    pub total_amounts_accepted: u32,
+    pub total_available_funds: u32,
+    pub minimum_bid_amount: u32,
+    pub is_initialized: bool,
+    pub holders_count: u32,
+    pub unit_cost_of_treasury_bonds: u32,
+    pub decimals: u8,
+    pub value_date: i64,
+    pub redemption_date: i64,
+    pub is_matured: bool,
+    pub deposit_account: Pubkey,
+    pub settlement_mint: Pubkey,
+    pub minimum_trade_amount: u32,
+    pub allows_early_redemption: bool,
+    pub day_count: u8,
+    pub bond_mint: Pubkey,
+    pub vault_tokens: Pubkey,
+    pub bump: u8,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pricing::{days_from_civil, SECONDS_PER_DAY};
+
+    fn date(year: i64, month: i64, day: i64) -> i64 {
+        days_from_civil(year, month, day) * SECONDS_PER_DAY
+    }
+
+    // 5 year zero coupon bond from 15-05-2024 to 15-05-2029 discounted at 11.25%
+    fn zero_coupon_bond() -> TreasuryBonds {
+        TreasuryBonds {
+            type_of_bond: ZERO_COUPON_BOND,
+            tenor: 5,
+            discount_rate: TreasuryBonds::zero_coupon_discount_rate(5),
+            value_date: date(2024, 5, 15),
+            redemption_date: date(2029, 5, 15),
+            ..Default::default()
+        }
+    }
+
+    fn assert_close(actual: u64, expected: u64, tolerance: u64) {
+        assert!(
+            actual.abs_diff(expected) <= tolerance,
+            "{actual} is not within {tolerance} of {expected}"
+        );
+    }
+
+    #[test]
+    fn zero_coupon_bond_accretes_to_par() {
+        let treasury_bonds = zero_coupon_bond();
+        let face_value = 10_000_000_000;
+
+        // at issue i.e 10 / 1.05625 ^ 10 = 5.785392
+        let price_at_issue = treasury_bonds
+            .purchase_price(face_value, date(2024, 5, 15))
+            .unwrap();
+        assert_close(price_at_issue, 5_785_391_863, 1_000);
+
+        // half way to redemption i.e 10 / 1.05625 ^ 5 = 7.606176
+        let price = treasury_bonds
+            .purchase_price(face_value, date(2026, 11, 15))
+            .unwrap();
+        assert_close(price, 7_606_176_347, 1_000);
+
+        // a day before redemption, a day of the last 181 day period is left
+        // i.e 10 / (1 + 5.625% / 181) = 9.996893
+        let price = treasury_bonds
+            .purchase_price(face_value, date(2029, 5, 14))
+            .unwrap();
+        assert_close(price, 9_996_893_231, 1_000);
+    }
+
+    #[test]
+    fn face_value_is_scaled_by_the_decimals() {
+        let mut treasury_bonds = TreasuryBonds {
+            decimals: 12,
+            ..Default::default()
+        };
+        assert_eq!(treasury_bonds.face_value_of(3), Some(3_000_000_000_000));
+
+        // face values that do not fit in the smallest unit are rejected rather than wrapped
+        treasury_bonds.decimals = 20;
+        assert_eq!(treasury_bonds.face_value_of(1), None);
+    }
+
+    #[test]
+    fn other_bonds_are_bought_at_par() {
+        let treasury_bonds = TreasuryBonds {
+            type_of_bond: FIXED_COUPON_BOND,
+            ..zero_coupon_bond()
+        };
+
+        assert_eq!(
+            treasury_bonds.purchase_price(10_000_000_000, date(2026, 11, 15)),
+            Some(10_000_000_000)
+        );
+    }
 }
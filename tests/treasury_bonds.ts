@@ -1,12 +1,10 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { TreasuryBonds } from "../target/types/treasury_bonds";
-import {
-  Account,
-  createAccount,
-  getOrCreateAssociatedTokenAccount,
-} from "@solana/spl-token";
+import { createAccount } from "@solana/spl-token";
 import { TOKEN_PROGRAM_ID } from "@coral-xyz/anchor/dist/cjs/utils/token";
+import { createHash } from "crypto";
+import { assert } from "chai";
 
 describe("treasury_bonds", () => {
   // Configure the client to use the local cluster.
@@ -38,8 +36,6 @@ describe("treasury_bonds", () => {
   let secondInvestorOwner = anchor.web3.Keypair.generate();
   let secondInvestorOwnerATA = anchor.web3.Keypair.generate();
 
-  let treasuryVaultATA: Account;
-
   // pdaAuth
   let [pdaAuth, adminPdaBump] = anchor.web3.PublicKey.findProgramAddressSync(
     [
@@ -54,15 +50,171 @@ describe("treasury_bonds", () => {
       program.programId
     );
 
+  // token account of the treasury vault, created when the treasury bonds are registered
+  const vaultTokens = anchor.utils.token.associatedAddress({
+    mint: mintToken.publicKey,
+    owner: treasuryVault,
+  });
+
   let [treasuryBondsConfigs] = anchor.web3.PublicKey.findProgramAddressSync(
     [anchor.utils.bytes.utf8.encode("treasury-bonds-configs")],
     program.programId
   );
 
+  // program data account of the upgradeable program, holds its upgrade authority
+  let [programData] = anchor.web3.PublicKey.findProgramAddressSync(
+    [program.programId.toBuffer()],
+    new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+  );
+
+  const issueNo = "FXD1/2024/05"; // issue no of bond
+
   let [treasuryBonds] = anchor.web3.PublicKey.findProgramAddressSync(
     [
       anchor.utils.bytes.utf8.encode("treasury-bonds"),
       treasuryBondsOwner.publicKey.toBuffer(),
+      anchor.utils.bytes.utf8.encode(issueNo),
+    ],
+    program.programId
+  );
+
+  let [couponSchedule] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode("coupon-schedule"),
+      treasuryBonds.toBuffer(),
+    ],
+    program.programId
+  );
+
+  // principal tranches repaid before maturity (amortizing infrastructure bonds)
+  let [amortizationSchedule] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode("amortization-schedule"),
+      treasuryBonds.toBuffer(),
+    ],
+    program.programId
+  );
+
+  // proposals are seeded by their proposal id i.e the sequence in which they are created
+  const proposalAddress = (proposalId: number) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        anchor.utils.bytes.utf8.encode("proposal"),
+        new anchor.BN(proposalId).toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    )[0];
+
+  // admin actions executed through an approved proposal
+  const registerProposal = proposalAddress(0);
+  const withdrawVaultProposal = proposalAddress(1);
+  const declareMaturityProposal = proposalAddress(2);
+
+  // the approvers (2 of 2) propose and sign off an admin action,
+  // payload is the serialized instruction params of the action
+  const proposeAndApprove = async (
+    proposal: anchor.web3.PublicKey,
+    action: number,
+    target: anchor.web3.PublicKey,
+    payload: Buffer
+  ) => {
+    let initParams = {
+      action: action, // 1 - register treasury bonds, 2 - declare maturity, 3 - withdraw vault, 4 - set approvers, 5 - update tax profile
+      target: target,
+      payloadHash: Array.from(createHash("sha256").update(payload).digest()),
+    };
+
+    const tx = await program.methods
+      .createProposal(initParams)
+      .accounts({
+        owner: adminOwner.publicKey,
+        treasuryBondsConfigs: treasuryBondsConfigs,
+        proposal: proposal,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([adminOwner])
+      .rpc();
+    console.log("Your transaction signature", tx);
+
+    const tx2 = await program.methods
+      .approveProposal()
+      .accounts({
+        owner: treasuryBondsOwner.publicKey,
+        treasuryBondsConfigs: treasuryBondsConfigs,
+        proposal: proposal,
+      })
+      .signers([treasuryBondsOwner])
+      .rpc();
+    console.log("Your transaction signature", tx2);
+  };
+
+  let [auction] = anchor.web3.PublicKey.findProgramAddressSync(
+    [anchor.utils.bytes.utf8.encode("auction"), treasuryBonds.toBuffer()],
+    program.programId
+  );
+
+  const escrowTokens = anchor.utils.token.associatedAddress({
+    mint: mintToken.publicKey,
+    owner: auction,
+  });
+
+  let [bondMint] = anchor.web3.PublicKey.findProgramAddressSync(
+    [anchor.utils.bytes.utf8.encode("bond-mint"), treasuryBonds.toBuffer()],
+    program.programId
+  );
+
+  // investor accounts checked by the transfer hook of the bond mint (Token-2022 mode)
+  let [extraAccountMetaList] = anchor.web3.PublicKey.findProgramAddressSync(
+    [anchor.utils.bytes.utf8.encode("extra-account-metas"), bondMint.toBuffer()],
+    program.programId
+  );
+
+  // withholding tax applied to coupons of the treasury bonds
+  let [taxProfile] = anchor.web3.PublicKey.findProgramAddressSync(
+    [anchor.utils.bytes.utf8.encode("tax-profile"), treasuryBonds.toBuffer()],
+    program.programId
+  );
+
+  // secondary market order book, escrowing bids' USDC and asks' bond units
+  let [orderBook] = anchor.web3.PublicKey.findProgramAddressSync(
+    [anchor.utils.bytes.utf8.encode("order-book"), treasuryBonds.toBuffer()],
+    program.programId
+  );
+
+  const orderBookEscrowTokens = anchor.utils.token.associatedAddress({
+    mint: mintToken.publicKey,
+    owner: orderBook,
+  });
+
+  const orderBookEscrowBondTokens = anchor.utils.token.associatedAddress({
+    mint: bondMint,
+    owner: orderBook,
+  });
+
+  // orders are seeded by their order id i.e the sequence in which they are placed
+  let [askOrder] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode("order"),
+      orderBook.toBuffer(),
+      new anchor.BN(1).toArrayLike(Buffer, "le", 8),
+    ],
+    program.programId
+  );
+
+  let [bidOrder] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode("order"),
+      orderBook.toBuffer(),
+      new anchor.BN(2).toArrayLike(Buffer, "le", 8),
+    ],
+    program.programId
+  );
+
+  let [evictedAskOrder] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode("order"),
+      orderBook.toBuffer(),
+      new anchor.BN(3).toArrayLike(Buffer, "le", 8),
     ],
     program.programId
   );
@@ -83,6 +235,150 @@ describe("treasury_bonds", () => {
     program.programId
   );
 
+  let [firstInvestorHolding] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode("holding"),
+      firstInvestor.toBuffer(),
+      treasuryBonds.toBuffer(),
+    ],
+    program.programId
+  );
+
+  let [secondInvestorHolding] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode("holding"),
+      secondInvestor.toBuffer(),
+      treasuryBonds.toBuffer(),
+    ],
+    program.programId
+  );
+
+  let [secondInvestorTaxRecord] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode("tax-record"),
+      secondInvestor.toBuffer(),
+    ],
+    program.programId
+  );
+
+  let [firstInvestorBid] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode("bid"),
+      auction.toBuffer(),
+      firstInvestor.toBuffer(),
+    ],
+    program.programId
+  );
+
+  let [firstInvestorOffer] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode("offer"),
+      treasuryBonds.toBuffer(),
+      firstInvestor.toBuffer(),
+    ],
+    program.programId
+  );
+
+  // bond units held by the investors
+  const firstInvestorBondTokens = anchor.utils.token.associatedAddress({
+    mint: bondMint,
+    owner: firstInvestorOwner.publicKey,
+  });
+
+  const secondInvestorBondTokens = anchor.utils.token.associatedAddress({
+    mint: bondMint,
+    owner: secondInvestorOwner.publicKey,
+  });
+
+  // zero coupon bonds issued at a discount to face value and redeemed at par
+  const zeroCouponIssueNo = "ZCB1/2024/05"; // issue no of bond
+  const zeroCouponDepositAccount = anchor.web3.Keypair.generate();
+
+  let [zeroCouponTreasuryBonds] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode("treasury-bonds"),
+      treasuryBondsOwner.publicKey.toBuffer(),
+      anchor.utils.bytes.utf8.encode(zeroCouponIssueNo),
+    ],
+    program.programId
+  );
+
+  let [zeroCouponCouponSchedule] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode("coupon-schedule"),
+      zeroCouponTreasuryBonds.toBuffer(),
+    ],
+    program.programId
+  );
+
+  let [zeroCouponAmortizationSchedule] =
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        anchor.utils.bytes.utf8.encode("amortization-schedule"),
+        zeroCouponTreasuryBonds.toBuffer(),
+      ],
+      program.programId
+    );
+
+  let [zeroCouponPdaAuth] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode("auth"),
+      zeroCouponDepositAccount.publicKey.toBuffer(),
+    ],
+    program.programId
+  );
+
+  let [zeroCouponTreasuryVault] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode("treasury-vault"),
+      zeroCouponPdaAuth.toBuffer(),
+    ],
+    program.programId
+  );
+
+  const zeroCouponVaultTokens = anchor.utils.token.associatedAddress({
+    mint: mintToken.publicKey,
+    owner: zeroCouponTreasuryVault,
+  });
+
+  let [zeroCouponBondMint] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode("bond-mint"),
+      zeroCouponTreasuryBonds.toBuffer(),
+    ],
+    program.programId
+  );
+
+  let [zeroCouponExtraAccountMetaList] =
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        anchor.utils.bytes.utf8.encode("extra-account-metas"),
+        zeroCouponBondMint.toBuffer(),
+      ],
+      program.programId
+    );
+
+  let [firstInvestorZeroCouponHolding] =
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        anchor.utils.bytes.utf8.encode("holding"),
+        firstInvestor.toBuffer(),
+        zeroCouponTreasuryBonds.toBuffer(),
+      ],
+      program.programId
+    );
+
+  const firstInvestorZeroCouponBondTokens =
+    anchor.utils.token.associatedAddress({
+      mint: zeroCouponBondMint,
+      owner: firstInvestorOwner.publicKey,
+    });
+
+  const registerZeroCouponProposal = proposalAddress(3);
+  const declareZeroCouponMaturityProposal = proposalAddress(4);
+  const singleApprovalProposal = proposalAddress(5);
+  const updateTaxProfileProposal = proposalAddress(6);
+
   // admin owner
   before(async () => {
     let res = await provider.connection.requestAirdrop(
@@ -149,13 +445,73 @@ describe("treasury_bonds", () => {
 
   it("Is initialized!", async () => {
     try {
+      let initParams = {
+        admin: adminOwner.publicKey,
+      };
+
+      // only the upgrade authority of the program (provider wallet) can initialize the configs
       const tx = await program.methods
-        .init()
+        .init(initParams)
         .accounts({
-          owner: adminOwner.publicKey,
+          owner: payer.publicKey,
           treasuryBondsConfigs: treasuryBondsConfigs,
+          program: program.programId,
+          programData: programData,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    try {
+      let result = await program.account.treasuryBondsConfigs.fetch(
+        treasuryBondsConfigs
+      );
+      console.log("treasuryBondsConfigs: ", result);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is create token!", async () => {
+    console.log("mint token: ", mintToken.publicKey.toBase58());
+    console.log("token account: ", tokenAccount.toBase58());
+
+    try {
+      let initParams = {
+        amount: new anchor.BN(200),
+        decimals: 9, // token mint in smallest unit i.e 9 decimals
+      };
+
+      const tx = await program.methods
+        .createToken(initParams)
+        .accounts({
+          owner: payer.publicKey,
+          mintToken: mintToken.publicKey,
+          tokenAccount: tokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associateTokenProgram: associateTokenProgram,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
+        .signers([mintToken])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is add settlement mint!", async () => {
+    try {
+      const tx = await program.methods
+        .addSettlementMint()
+        .accounts({
+          owner: adminOwner.publicKey,
+          treasuryBondsConfigs: treasuryBondsConfigs,
+          mintToken: mintToken.publicKey,
+        })
         .signers([adminOwner])
         .rpc();
       console.log("Your transaction signature", tx);
@@ -173,10 +529,137 @@ describe("treasury_bonds", () => {
     }
   });
 
+  it("Is grant roles!", async () => {
+    // 1 - issuance operator, 2 - treasurer, 3 - compliance officer
+    const roles = [
+      { role: 1, account: treasuryBondsOwner.publicKey },
+      { role: 2, account: payer.publicKey },
+      { role: 3, account: adminOwner.publicKey },
+    ];
+
+    for (const initParams of roles) {
+      await program.methods
+        .grantRole(initParams)
+        .accounts({
+          owner: adminOwner.publicKey,
+          treasuryBondsConfigs: treasuryBondsConfigs,
+        })
+        .signers([adminOwner])
+        .rpc();
+    }
+
+    let result = await program.account.treasuryBondsConfigs.fetch(
+      treasuryBondsConfigs
+    );
+    assert.equal(result.roles.length, roles.length);
+    for (const { role, account } of roles) {
+      assert.isTrue(
+        result.roles.some(
+          (grant) => grant.role == role && grant.account.equals(account)
+        )
+      );
+    }
+
+    // only the configs admin grants roles
+    let errorCode = "";
+    try {
+      await program.methods
+        .grantRole({ role: 2, account: treasuryBondsOwner.publicKey })
+        .accounts({
+          owner: treasuryBondsOwner.publicKey,
+          treasuryBondsConfigs: treasuryBondsConfigs,
+        })
+        .signers([treasuryBondsOwner])
+        .rpc();
+    } catch (error) {
+      errorCode = error.error.errorCode.code;
+    }
+    assert.equal(errorCode, "InvalidAdmin");
+  });
+
+  it("Is set approvers!", async () => {
+    let initParams = {
+      approvers: [adminOwner.publicKey, treasuryBondsOwner.publicKey],
+      approvalThreshold: 2, // M of N approvers
+      proposalTimeout: new anchor.BN(24 * 60 * 60), // one day
+    };
+
+    // the first approvers are set by the configs admin without a proposal
+    await program.methods
+      .setApprovers(initParams)
+      .accounts({
+        owner: adminOwner.publicKey,
+        treasuryBondsConfigs: treasuryBondsConfigs,
+        proposal: null,
+      })
+      .signers([adminOwner])
+      .rpc();
+
+    let result = await program.account.treasuryBondsConfigs.fetch(
+      treasuryBondsConfigs
+    );
+    assert.equal(result.approvers.length, 2);
+    assert.ok(result.approvers[0].equals(adminOwner.publicKey));
+    assert.ok(result.approvers[1].equals(treasuryBondsOwner.publicKey));
+    assert.equal(result.approvalThreshold, 2);
+  });
+
+  it("Is single key approvers rejected!", async () => {
+    let errorCode = "";
+    try {
+      let initParams = {
+        approvers: [adminOwner.publicKey],
+        approvalThreshold: 1, // a single approver acting alone
+        proposalTimeout: new anchor.BN(24 * 60 * 60), // one day
+      };
+
+      await program.methods
+        .setApprovers(initParams)
+        .accounts({
+          owner: adminOwner.publicKey,
+          treasuryBondsConfigs: treasuryBondsConfigs,
+          proposal: null,
+        })
+        .signers([adminOwner])
+        .rpc();
+    } catch (error) {
+      errorCode = error.error.errorCode.code;
+    }
+
+    assert.equal(errorCode, "InvalidApprovers");
+  });
+
+  it("Is approvers change without a proposal rejected!", async () => {
+    let errorCode = "";
+    try {
+      let initParams = {
+        approvers: [adminOwner.publicKey, payer.publicKey],
+        approvalThreshold: 2, // M of N approvers
+        proposalTimeout: new anchor.BN(24 * 60 * 60), // one day
+      };
+
+      // approvers are already set, the admin can not replace them alone
+      await program.methods
+        .setApprovers(initParams)
+        .accounts({
+          owner: adminOwner.publicKey,
+          treasuryBondsConfigs: treasuryBondsConfigs,
+          proposal: null,
+        })
+        .signers([adminOwner])
+        .rpc();
+    } catch (error) {
+      errorCode = error.error.errorCode.code;
+    }
+
+    assert.equal(errorCode, "ProposalNotApproved");
+  });
+
   it("Is register treasury bonds!", async () => {
     // typeOfBond
     // 1 - Fixed coupon Treasury bonds
     // 2 - Infrastructure bonds
+    // 3 - Zero coupon bonds
 
     try {
       let bondIssuer = {
@@ -186,27 +669,51 @@ describe("treasury_bonds", () => {
       let initParams = {
         issuer: bondIssuer,
         country: "KE",
-        issueNo: "FXD1/2024/05",
-        typeOfBond: 1, // 1 - Fixed coupon Treasury bonds, 2 - Infrastructure bonds
+        issueNo: issueNo,
+        typeOfBond: 1, // 1 - Fixed coupon Treasury bonds, 2 - Infrastructure bonds, 3 - Zero coupon bonds
         tenor: 5, // years
-        couponRate: 12, // %
+        couponRate: 129655, // ten-thousandths of a percent i.e 12.9655%
         totalAmountsOffered: 100, // USD
         minimumBidAmount: 1, // USD
+        minimumTradeAmount: 0, // USD, zero defaults to the minimum bid amount
+        allowsEarlyRedemption: false, // redeem before maturity
+        dayCount: 2, // day-count convention i.e 1 - Actual/365, 2 - Actual/Actual (ICMA), 3 - 30/360
         unitCostOfTreasuryBonds: 1, // unit cost of treasury bonds
         decimals: 9, // token mint in smallest unit i.e 9 decimals
-        valueDate: "15-05-2024",
-        redemptionDate: "15-05-2029",
+        valueDate: new anchor.BN(1715731200), // 15-05-2024
+        redemptionDate: new anchor.BN(1873497600), // 15-05-2029
+        transferHook: false, // true restricts transfers of bond units to registered investors (Token-2022 only)
+        // principal tranches of infrastructure bonds e.g [{ repaymentDate: new anchor.BN(1810339200), repaymentRate: 5000 }],
+        // empty for fixed coupon bonds i.e face value repaid at maturity
+        amortizationSchedule: [],
       };
 
+      await proposeAndApprove(
+        registerProposal,
+        1,
+        treasuryBonds,
+        program.coder.types.encode("RegisterTreasuryBondsParams", initParams)
+      );
+
       const tx = await program.methods
         .registerTreasuryBonds(initParams)
         .accounts({
           owner: treasuryBondsOwner.publicKey,
           treasuryBondsConfigs: treasuryBondsConfigs,
+          proposal: registerProposal,
           treasuryBonds: treasuryBonds,
+          couponSchedule: couponSchedule,
+          amortizationSchedule: amortizationSchedule,
           depositAccount: depositAccount.publicKey,
           pdaAuth: pdaAuth,
           treasuryVault: treasuryVault,
+          vaultTokens: vaultTokens,
+          mintToken: mintToken.publicKey,
+          bondMint: bondMint,
+          extraAccountMetaList: extraAccountMetaList,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          bondTokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: associateTokenProgram,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([treasuryBondsOwner, depositAccount])
@@ -224,37 +731,11 @@ describe("treasury_bonds", () => {
       let result3 = await program.account.treasuryBondsConfigs.fetch(
         treasuryBondsConfigs
       );
+      let result4 = await program.account.couponSchedule.fetch(couponSchedule);
       console.log("treasury bonds: ", result);
       console.log("deposit account: ", result2);
       console.log("treasury bonds configs: ", result3);
-    } catch (error) {
-      console.log(error);
-    }
-  });
-
-  it("Is create token!", async () => {
-    console.log("mint token: ", mintToken.publicKey.toBase58());
-    console.log("token account: ", tokenAccount.toBase58());
-
-    try {
-      let initParams = {
-        amount: new anchor.BN(200),
-      };
-
-      const tx = await program.methods
-        .createToken(initParams)
-        .accounts({
-          owner: payer.publicKey,
-          treasuryBonds: treasuryBonds,
-          mintToken: mintToken.publicKey,
-          tokenAccount: tokenAccount,
-          tokenProgram: TOKEN_PROGRAM_ID,
-          associateTokenProgram: associateTokenProgram,
-          systemProgram: anchor.web3.SystemProgram.programId,
-        })
-        .signers([mintToken])
-        .rpc();
-      console.log("Your transaction signature", tx);
+      console.log("coupon schedule: ", result4);
     } catch (error) {
       console.log(error);
     }
@@ -294,7 +775,6 @@ describe("treasury_bonds", () => {
           associateTokenProgram: associateTokenProgram,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
-        .signers([mintToken])
         .rpc();
 
       console.log("Your transaction signature", tx);
@@ -337,7 +817,6 @@ describe("treasury_bonds", () => {
           associateTokenProgram: associateTokenProgram,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
-        .signers([mintToken])
         .rpc();
 
       console.log("Your transaction signature", tx);
@@ -404,22 +883,246 @@ describe("treasury_bonds", () => {
     }
   });
 
-  it("Is buy treasury bonds!", async () => {
+  it("Is set investor status!", async () => {
     try {
-      treasuryVaultATA = await getOrCreateAssociatedTokenAccount(
-        provider.connection,
-        payer,
-        mintToken.publicKey,
-        treasuryVault,
-        true
-      );
-      console.log(
-        "treasuryVaultATA address: " + treasuryVaultATA.address.toBase58()
-      );
+      let initParams = {
+        active: true, // inactive investors can not trade or receive payouts
+      };
+
+      const tx = await program.methods
+        .setInvestorStatus(initParams)
+        .accounts({
+          owner: adminOwner.publicKey,
+          treasuryBondsConfigs: treasuryBondsConfigs,
+          investor: firstInvestor,
+        })
+        .signers([adminOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
     } catch (error) {
       console.log(error);
     }
 
+    try {
+      let result = await program.account.investor.fetch(firstInvestor);
+      console.log("investor: ", result);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is open auction!", async () => {
+    let now = Math.floor(Date.now() / 1000);
+    let initParams = {
+      bidOpeningDate: new anchor.BN(now),
+      bidClosingDate: new anchor.BN(now + 5), // bids accepted for 5 seconds
+    };
+
+    await program.methods
+      .openAuction(initParams)
+      .accounts({
+        treasuryBondsConfigs: treasuryBondsConfigs,
+        owner: treasuryBondsOwner.publicKey,
+        treasuryBonds: treasuryBonds,
+        auction: auction,
+        escrowTokens: escrowTokens,
+        mintToken: mintToken.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: associateTokenProgram,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([treasuryBondsOwner])
+      .rpc();
+
+    let result = await program.account.auction.fetch(auction);
+    assert.isTrue(result.isInitialized);
+    assert.isFalse(result.isClosed);
+    assert.equal(result.bidClosingDate.toNumber(), now + 5);
+  });
+
+  it("Is submit bid!", async () => {
+    const submitBid = async (yieldRate: number) => {
+      await program.methods
+        .submitBid({
+          amount: 10, // USD
+          yieldRate: yieldRate, // basis points i.e 12.5%, null for a non-competitive bid
+        })
+        .accounts({
+          owner: firstInvestorOwner.publicKey,
+          treasuryBonds: treasuryBonds,
+          couponSchedule: couponSchedule,
+          amortizationSchedule: amortizationSchedule,
+          auction: auction,
+          bid: firstInvestorBid,
+          investor: firstInvestor,
+          senderTokens: firstInvestorOwnerATA.publicKey,
+          escrowTokens: escrowTokens,
+          mintToken: mintToken.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([firstInvestorOwner])
+        .rpc();
+    };
+
+    // competitive bids carry a yield
+    let errorCode = "";
+    try {
+      await submitBid(0);
+    } catch (error) {
+      errorCode = error.error.errorCode.code;
+    }
+    assert.equal(errorCode, "InvalidBidYield");
+
+    await submitBid(1250);
+
+    let result = await program.account.bid.fetch(firstInvestorBid);
+    assert.isTrue(result.isCompetitive);
+    assert.equal(result.yieldRate, 1250);
+    assert.equal(result.amount, 10);
+    assert.isAbove(result.escrowedAmount.toNumber(), 0);
+
+    let result2 = await program.account.auction.fetch(auction);
+    assert.equal(result2.totalCompetitiveBids, 10);
+  });
+
+  it("Is close auction!", async () => {
+    const listener = program.addEventListener("auctionClosed", (event) => {
+      console.log("auction closed: ", event);
+    });
+
+    const closeAuction = async () => {
+      await program.methods
+        .closeAuction()
+        .accounts({
+          treasuryBondsConfigs: treasuryBondsConfigs,
+          owner: treasuryBondsOwner.publicKey,
+          treasuryBonds: treasuryBonds,
+          couponSchedule: couponSchedule,
+          amortizationSchedule: amortizationSchedule,
+          auction: auction,
+        })
+        .signers([treasuryBondsOwner])
+        .rpc();
+    };
+
+    // auction can not be closed while bids are accepted
+    let errorCode = "";
+    try {
+      await closeAuction();
+    } catch (error) {
+      errorCode = error.error.errorCode.code;
+    }
+    assert.equal(errorCode, "AuctionNotClosed");
+
+    // wait for the bid window to close
+    await new Promise((resolve) => setTimeout(resolve, 6000));
+
+    await closeAuction();
+
+    // the bid is below the amount offered, so it is allotted in full at its yield
+    let result = await program.account.auction.fetch(auction);
+    assert.isTrue(result.isClosed);
+    assert.equal(result.cutOffYield, 1250);
+    assert.isNotNull(result.clearingPrice);
+
+    await program.removeEventListener(listener);
+  });
+
+  it("Is settle bid of a deactivated investor limited to refunds!", async () => {
+    const setFirstInvestorStatus = async (active: boolean) => {
+      await program.methods
+        .setInvestorStatus({ active: active })
+        .accounts({
+          owner: adminOwner.publicKey,
+          treasuryBondsConfigs: treasuryBondsConfigs,
+          investor: firstInvestor,
+        })
+        .signers([adminOwner])
+        .rpc();
+    };
+
+    // the first investor's bid is allotted, so it cannot be settled while deactivated
+    await setFirstInvestorStatus(false);
+
+    let errorCode = "";
+    try {
+      await program.methods
+        .settleBid()
+        .accounts({
+          owner: firstInvestorOwner.publicKey,
+          treasuryBonds: treasuryBonds,
+          couponSchedule: couponSchedule,
+          amortizationSchedule: amortizationSchedule,
+          auction: auction,
+          bid: firstInvestorBid,
+          investor: firstInvestor,
+          holding: firstInvestorHolding,
+          escrowTokens: escrowTokens,
+          recipientTokens: vaultTokens,
+          refundTokens: firstInvestorOwnerATA.publicKey,
+          mintToken: mintToken.publicKey,
+          bondMint: bondMint,
+          bondTokens: firstInvestorBondTokens,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          bondTokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: associateTokenProgram,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([firstInvestorOwner])
+        .rpc();
+    } catch (error) {
+      errorCode = error.error.errorCode.code;
+    }
+    assert.equal(errorCode, "InvalidInvestorStatus");
+
+    await setFirstInvestorStatus(true);
+  });
+
+  it("Is settle bid!", async () => {
+    await program.methods
+      .settleBid()
+      .accounts({
+          owner: firstInvestorOwner.publicKey,
+          treasuryBonds: treasuryBonds,
+          couponSchedule: couponSchedule,
+          amortizationSchedule: amortizationSchedule,
+          auction: auction,
+          bid: firstInvestorBid,
+          investor: firstInvestor,
+          holding: firstInvestorHolding,
+          escrowTokens: escrowTokens,
+          recipientTokens: vaultTokens,
+          refundTokens: firstInvestorOwnerATA.publicKey,
+          mintToken: mintToken.publicKey,
+          bondMint: bondMint,
+          bondTokens: firstInvestorBondTokens,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          bondTokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: associateTokenProgram,
+          systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([firstInvestorOwner])
+      .rpc();
+
+    let result = await program.account.holding.fetch(firstInvestorHolding);
+    assert.equal(result.availableFunds, 10);
+
+    let result2 = await program.account.treasuryBonds.fetch(treasuryBonds);
+    assert.equal(result2.holdersCount, 1);
+
+    // bid is closed once settled
+    assert.isNull(await program.account.bid.fetchNullable(firstInvestorBid));
+  });
+
+  it("Is buy treasury bonds!", async () => {
+    const listener = program.addEventListener(
+      "treasuryBondsPurchased",
+      (event) => {
+        console.log("treasury bonds purchased: ", event);
+      }
+    );
+
     try {
       let initParams = {
         // 10 amount of token to transfer (in smallest unit i.e 9 decimals)
@@ -431,12 +1134,18 @@ describe("treasury_bonds", () => {
         .accounts({
           owner: firstInvestorOwner.publicKey,
           treasuryBonds: treasuryBonds,
+          couponSchedule: couponSchedule,
+          amortizationSchedule: amortizationSchedule,
           investor: firstInvestor,
+          holding: firstInvestorHolding,
           senderTokens: firstInvestorOwnerATA.publicKey,
-          recipientTokens: treasuryVaultATA.address,
+          recipientTokens: vaultTokens,
           mintToken: mintToken.publicKey,
+          bondMint: bondMint,
+          bondTokens: firstInvestorBondTokens,
           tokenProgram: TOKEN_PROGRAM_ID,
-          associateTokenProgram: associateTokenProgram,
+          bondTokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: associateTokenProgram,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([firstInvestorOwner])
@@ -447,37 +1156,151 @@ describe("treasury_bonds", () => {
     }
 
     try {
-      let result = await program.account.investor.fetch(firstInvestor);
-      console.log("investor: ", result);
+      let result = await program.account.holding.fetch(firstInvestorHolding);
+      console.log("holding: ", result);
 
       let result2 = await program.account.treasuryBonds.fetch(treasuryBonds);
       console.log("treasury bonds: ", result2);
     } catch (error) {
       console.log(error);
     }
+
+    await program.removeEventListener(listener);
+  });
+
+  it("Is buy above the amount offered rejected!", async () => {
+    let errorCode = "";
+    try {
+      let initParams = {
+        // 1000 exceeds the 100 offered for the treasury bonds
+        amount: new anchor.BN(1000),
+      };
+
+      await program.methods
+        .buyTreasuryBonds(initParams)
+        .accounts({
+          owner: firstInvestorOwner.publicKey,
+          treasuryBonds: treasuryBonds,
+          couponSchedule: couponSchedule,
+          amortizationSchedule: amortizationSchedule,
+          investor: firstInvestor,
+          holding: firstInvestorHolding,
+          senderTokens: firstInvestorOwnerATA.publicKey,
+          recipientTokens: vaultTokens,
+          mintToken: mintToken.publicKey,
+          bondMint: bondMint,
+          bondTokens: firstInvestorBondTokens,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          bondTokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: associateTokenProgram,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([firstInvestorOwner])
+        .rpc();
+    } catch (error) {
+      errorCode = error.error.errorCode.code;
+    }
+
+    assert.equal(errorCode, "AmountOfferedExceeded");
+  });
+
+  it("Is create offer!", async () => {
+    try {
+      let initParams = {
+        amount: 20, // USD
+        cleanPrice: 9950, // clean price per 100 face value i.e 99.50
+        expiryDate: new anchor.BN(Math.floor(Date.now() / 1000) + 24 * 60 * 60), // 1 day
+      };
+
+      const tx = await program.methods
+        .createOffer(initParams)
+        .accounts({
+          owner: firstInvestorOwner.publicKey,
+          treasuryBonds: treasuryBonds,
+          investor: firstInvestor,
+          holding: firstInvestorHolding,
+          offer: firstInvestorOffer,
+          bondMint: bondMint,
+          bondTokens: firstInvestorBondTokens,
+          bondTokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([firstInvestorOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    try {
+      let result = await program.account.offer.fetch(firstInvestorOffer);
+      console.log("offer: ", result);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is quote price yield!", async () => {
+    try {
+      let initParams = {
+        cleanPrice: 9950, // clean price per 100 face value i.e 99.50
+        yieldRate: null, // annual yield (basis points)
+      };
+
+      // read-only, the quote is returned through return data
+      const result = await program.methods
+        .quotePriceYield(initParams)
+        .accounts({
+          treasuryBonds: treasuryBonds,
+          couponSchedule: couponSchedule,
+          amortizationSchedule: amortizationSchedule,
+        })
+        .view();
+      console.log("price yield quote: ", result);
+    } catch (error) {
+      console.log(error);
+    }
   });
 
   it("Is sell treasury bonds!", async () => {
+    // settlement amounts i.e clean and dirty price of the sale
+    const listener = program.addEventListener(
+      "treasuryBondsSold",
+      (event) => {
+        console.log("treasury bonds sold: ", event);
+      }
+    );
+
     try {
       let initParams = {
-        // 10 amount of token to transfer (in smallest unit i.e 9 decimals)
-        amount: new anchor.BN(10),
+        amount: 20, // USD
+        cleanPrice: 9950, // agreed clean price per 100 face value i.e 99.50
       };
       const tx = await program.methods
         .sellTreasuryBonds(initParams)
         .accounts({
           owner: secondInvestorOwner.publicKey,
           treasuryBonds: treasuryBonds,
+          couponSchedule: couponSchedule,
+          amortizationSchedule: amortizationSchedule,
+          offer: firstInvestorOffer,
           sellerInvestor: firstInvestor,
+          sellerHolding: firstInvestorHolding,
           buyerInvestor: secondInvestor,
+          buyerHolding: secondInvestorHolding,
           mintToken: mintToken.publicKey,
+          bondMint: bondMint,
+          sellerBondTokens: firstInvestorBondTokens,
+          buyerBondTokens: secondInvestorBondTokens,
+          seller: firstInvestorOwner.publicKey,
           fromAccount: secondInvestorOwnerATA.publicKey, // buyer
           toAccount: firstInvestorOwnerATA.publicKey, // seller
           tokenProgram: TOKEN_PROGRAM_ID,
-          associateTokenProgram: associateTokenProgram,
+          bondTokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: associateTokenProgram,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
-        .signers([mintToken, secondInvestorOwner])
+        .signers([secondInvestorOwner])
         .rpc();
       console.log("Your transaction signature", tx);
     } catch (error) {
@@ -490,17 +1313,517 @@ describe("treasury_bonds", () => {
       );
       console.log("deposit account: ", result);
 
-      let result2 = await program.account.investor.fetch(firstInvestor);
-      console.log("first investor: ", result2);
+      let result2 = await program.account.holding.fetch(firstInvestorHolding);
+      console.log("first investor holding: ", result2);
 
-      let result3 = await program.account.investor.fetch(secondInvestor);
-      console.log("second investor: ", result3);
+      let result3 = await program.account.holding.fetch(
+        secondInvestorHolding
+      );
+      console.log("second investor holding: ", result3);
 
       let result4 = await program.account.treasuryBonds.fetch(treasuryBonds);
       console.log("treasury bonds: ", result4);
     } catch (error) {
       console.log(error);
     }
+
+    await program.removeEventListener(listener);
+  });
+
+  it("Is open order book!", async () => {
+    await program.methods
+      .openOrderBook()
+      .accounts({
+        treasuryBondsConfigs: treasuryBondsConfigs,
+        owner: treasuryBondsOwner.publicKey,
+        treasuryBonds: treasuryBonds,
+        orderBook: orderBook,
+        escrowTokens: orderBookEscrowTokens,
+        escrowBondTokens: orderBookEscrowBondTokens,
+        mintToken: mintToken.publicKey,
+        bondMint: bondMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        bondTokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: associateTokenProgram,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([treasuryBondsOwner])
+      .rpc();
+
+    let result = await program.account.orderBook.fetch(orderBook);
+    assert.isTrue(result.isInitialized);
+    assert.equal(result.nextOrderId.toNumber(), 1);
+    assert.equal(result.bids.length, 0);
+    assert.equal(result.asks.length, 0);
+  });
+
+  it("Is place ask order!", async () => {
+    const placeOrder = async (side: number) => {
+      await program.methods
+        .placeOrder({
+          side: side, // 1 - bid (buy) order, 2 - ask (sell) order
+          cleanPrice: 9950, // clean price per 100 face value i.e 99.50
+          amount: 10, // USD
+        })
+        .accounts({
+          owner: secondInvestorOwner.publicKey,
+          treasuryBonds: treasuryBonds,
+          couponSchedule: couponSchedule,
+          amortizationSchedule: amortizationSchedule,
+          orderBook: orderBook,
+          order: askOrder,
+          investor: secondInvestor,
+          holding: secondInvestorHolding,
+          senderTokens: secondInvestorOwnerATA.publicKey,
+          escrowTokens: orderBookEscrowTokens,
+          mintToken: mintToken.publicKey,
+          bondMint: bondMint,
+          bondTokens: secondInvestorBondTokens,
+          escrowBondTokens: orderBookEscrowBondTokens,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          bondTokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: associateTokenProgram,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([secondInvestorOwner])
+        .rpc();
+    };
+
+    // orders are either bids or asks
+    let errorCode = "";
+    try {
+      await placeOrder(3);
+    } catch (error) {
+      errorCode = error.error.errorCode.code;
+    }
+    assert.equal(errorCode, "InvalidOrderSide");
+
+    await placeOrder(2);
+
+    let result = await program.account.order.fetch(askOrder);
+    assert.equal(result.side, 2);
+    assert.equal(result.cleanPrice, 9950);
+    assert.equal(result.amount, 10);
+
+    let result2 = await program.account.orderBook.fetch(orderBook);
+    assert.equal(result2.asks.length, 1);
+    assert.equal(result2.asks[0].orderId.toNumber(), 1);
+  });
+
+  it("Is place bid order!", async () => {
+    const placeOrder = async (side: number) => {
+      await program.methods
+        .placeOrder({
+          side: side, // 1 - bid (buy) order, 2 - ask (sell) order
+          cleanPrice: 10000, // clean price per 100 face value i.e 100.00
+          amount: 10, // USD
+        })
+        .accounts({
+          owner: firstInvestorOwner.publicKey,
+          treasuryBonds: treasuryBonds,
+          couponSchedule: couponSchedule,
+          amortizationSchedule: amortizationSchedule,
+          orderBook: orderBook,
+          order: bidOrder,
+          investor: firstInvestor,
+          holding: firstInvestorHolding,
+          senderTokens: firstInvestorOwnerATA.publicKey,
+          escrowTokens: orderBookEscrowTokens,
+          mintToken: mintToken.publicKey,
+          bondMint: bondMint,
+          bondTokens: firstInvestorBondTokens,
+          escrowBondTokens: orderBookEscrowBondTokens,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          bondTokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: associateTokenProgram,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([firstInvestorOwner])
+        .rpc();
+    };
+
+    await placeOrder(1);
+
+    let result = await program.account.order.fetch(bidOrder);
+    assert.equal(result.side, 1);
+    assert.equal(result.cleanPrice, 10000);
+    assert.isAbove(result.escrowedAmount.toNumber(), 0);
+
+    let result2 = await program.account.orderBook.fetch(orderBook);
+    assert.equal(result2.bids.length, 1);
+    assert.equal(result2.bids[0].orderId.toNumber(), 2);
+  });
+
+  it("Is match orders!", async () => {
+    await program.methods
+      .matchOrders()
+      .accounts({
+        treasuryBonds: treasuryBonds,
+        couponSchedule: couponSchedule,
+        amortizationSchedule: amortizationSchedule,
+        orderBook: orderBook,
+        bidOrder: bidOrder,
+        askOrder: askOrder,
+        buyerInvestor: firstInvestor,
+        sellerInvestor: secondInvestor,
+        buyerHolding: firstInvestorHolding,
+        sellerHolding: secondInvestorHolding,
+        escrowTokens: orderBookEscrowTokens,
+        buyerTokens: firstInvestorOwnerATA.publicKey,
+        sellerTokens: secondInvestorOwnerATA.publicKey,
+        mintToken: mintToken.publicKey,
+        bondMint: bondMint,
+        escrowBondTokens: orderBookEscrowBondTokens,
+        buyerBondTokens: firstInvestorBondTokens,
+        buyer: firstInvestorOwner.publicKey,
+        seller: secondInvestorOwner.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        bondTokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    // both orders are filled and taken off the book
+    let result = await program.account.orderBook.fetch(orderBook);
+    assert.equal(result.bids.length, 0);
+    assert.equal(result.asks.length, 0);
+
+    let result2 = await program.account.holding.fetch(firstInvestorHolding);
+    assert.equal(result2.availableFunds, 10);
+
+    let result3 = await program.account.holding.fetch(secondInvestorHolding);
+    assert.equal(result3.availableFunds, 10);
+  });
+
+  it("Is order of a deactivated investor evicted!", async () => {
+    const setFirstInvestorStatus = async (active: boolean) => {
+      await program.methods
+        .setInvestorStatus({ active: active })
+        .accounts({
+          owner: adminOwner.publicKey,
+          treasuryBondsConfigs: treasuryBondsConfigs,
+          investor: firstInvestor,
+        })
+        .signers([adminOwner])
+        .rpc();
+    };
+
+    const evictOrder = async () => {
+      await program.methods
+        .evictOrder()
+        .accounts({
+          treasuryBondsConfigs: treasuryBondsConfigs,
+          treasuryBonds: treasuryBonds,
+          couponSchedule: couponSchedule,
+          amortizationSchedule: amortizationSchedule,
+          orderBook: orderBook,
+          order: evictedAskOrder,
+          investor: firstInvestor,
+          holding: firstInvestorHolding,
+          owner: treasuryBondsOwner.publicKey,
+        })
+        .signers([treasuryBondsOwner])
+        .rpc();
+    };
+
+    await program.methods
+      .placeOrder({ side: 2, cleanPrice: 10100, amount: 5 })
+      .accounts({
+        owner: firstInvestorOwner.publicKey,
+        treasuryBonds: treasuryBonds,
+        couponSchedule: couponSchedule,
+        amortizationSchedule: amortizationSchedule,
+        orderBook: orderBook,
+        order: evictedAskOrder,
+        investor: firstInvestor,
+        holding: firstInvestorHolding,
+        senderTokens: firstInvestorOwnerATA.publicKey,
+        escrowTokens: orderBookEscrowTokens,
+        mintToken: mintToken.publicKey,
+        bondMint: bondMint,
+        bondTokens: firstInvestorBondTokens,
+        escrowBondTokens: orderBookEscrowBondTokens,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        bondTokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: associateTokenProgram,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([firstInvestorOwner])
+      .rpc();
+
+    // orders that can still be matched stay on the book
+    let errorCode = "";
+    try {
+      await evictOrder();
+    } catch (error) {
+      errorCode = error.error.errorCode.code;
+    }
+    assert.equal(errorCode, "OrderStillMatchable");
+
+    // the ask of a deactivated investor no longer holds up the orders behind it
+    await setFirstInvestorStatus(false);
+    await evictOrder();
+    await setFirstInvestorStatus(true);
+
+    let result = await program.account.orderBook.fetch(orderBook);
+    assert.isFalse(result.asks.some((entry) => entry.orderId.eqn(3)));
+
+    // evicted order keeps its escrow until it is cancelled
+    await program.methods
+      .cancelOrder()
+      .accounts({
+        treasuryBonds: treasuryBonds,
+        orderBook: orderBook,
+        order: evictedAskOrder,
+        refundTokens: firstInvestorOwnerATA.publicKey,
+        escrowTokens: orderBookEscrowTokens,
+        mintToken: mintToken.publicKey,
+        bondMint: bondMint,
+        bondTokens: firstInvestorBondTokens,
+        escrowBondTokens: orderBookEscrowBondTokens,
+        owner: firstInvestorOwner.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        bondTokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([firstInvestorOwner])
+      .rpc();
+
+    assert.isNull(
+      await program.account.order.fetchNullable(evictedAskOrder)
+    );
+  });
+
+  it("Is set tax profile!", async () => {
+    let initParams = {
+      withholdingTaxRate: null, // defaults by tenor i.e 15% below ten years
+    };
+
+    await program.methods
+      .setTaxProfile(initParams)
+      .accounts({
+        treasuryBondsConfigs: treasuryBondsConfigs,
+        owner: treasuryBondsOwner.publicKey,
+        treasuryBonds: treasuryBonds,
+        taxProfile: taxProfile,
+        taxAuthorityTokens: tokenAccount, // tax authority's token account (*for test purposes)
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([treasuryBondsOwner])
+      .rpc();
+
+    let result = await program.account.taxProfile.fetch(taxProfile);
+    assert.isFalse(result.isTaxExempt);
+    assert.equal(result.withholdingTaxRate, 1500);
+    assert.ok(result.taxAuthorityTokens.equals(tokenAccount));
+
+    // tax profile is only set once, it is changed through a proposal afterwards
+    let failed = false;
+    try {
+      await program.methods
+        .setTaxProfile({ withholdingTaxRate: 1000 })
+        .accounts({
+          treasuryBondsConfigs: treasuryBondsConfigs,
+          owner: treasuryBondsOwner.publicKey,
+          treasuryBonds: treasuryBonds,
+          taxProfile: taxProfile,
+          taxAuthorityTokens: tokenAccount,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([treasuryBondsOwner])
+        .rpc();
+    } catch (error) {
+      failed = true;
+    }
+    assert.isTrue(failed);
+  });
+
+  it("Is fund vault!", async () => {
+    try {
+      let initParams = {
+        amount: 10, // USD
+      };
+
+      const tx = await program.methods
+        .fundVault(initParams)
+        .accounts({
+          owner: payer.publicKey,
+          treasuryBondsConfigs: treasuryBondsConfigs,
+          treasuryBonds: treasuryBonds,
+          senderTokens: tokenAccount,
+          vaultTokens: vaultTokens,
+          mintToken: mintToken.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is withdraw vault!", async () => {
+    let initParams = {
+      amount: 5, // USD
+    };
+
+    const withdrawVault = async (recipientTokens: anchor.web3.PublicKey) => {
+      await program.methods
+        .withdrawVault(initParams)
+        .accounts({
+          owner: payer.publicKey,
+          treasuryBondsConfigs: treasuryBondsConfigs,
+          proposal: withdrawVaultProposal,
+          treasuryBonds: treasuryBonds,
+          senderTokens: vaultTokens,
+          recipientTokens: recipientTokens,
+          mintToken: mintToken.publicKey,
+          depositAccount: depositAccount.publicKey,
+          pdaAuth: pdaAuth,
+          treasuryVault: treasuryVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+    };
+
+    // payload is the serialized params followed by the recipient token account
+    await proposeAndApprove(
+      withdrawVaultProposal,
+      3,
+      treasuryBonds,
+      Buffer.concat([
+        program.coder.types.encode("WithdrawVaultParams", initParams),
+        tokenAccount.toBuffer(),
+      ])
+    );
+
+    // funds can only be withdrawn to the proposed recipient
+    let errorCode = "";
+    try {
+      await withdrawVault(firstInvestorOwnerATA.publicKey);
+    } catch (error) {
+      errorCode = error.error.errorCode.code;
+    }
+    assert.equal(errorCode, "ProposalMismatch");
+
+    await withdrawVault(tokenAccount);
+
+    let result = await program.account.proposal.fetch(withdrawVaultProposal);
+    assert.isTrue(result.isExecuted);
+    assert.equal(result.approvals.length, 2);
+
+    // proposals are executed once
+    errorCode = "";
+    try {
+      await withdrawVault(tokenAccount);
+    } catch (error) {
+      errorCode = error.error.errorCode.code;
+    }
+    assert.equal(errorCode, "ProposalAlreadyExecuted");
+  });
+
+  it("Is pay coupon!", async () => {
+    // coupons that fell due before the second investor bought the treasury bonds are not theirs
+    let errorCode = "";
+    try {
+      await program.methods
+        .payCoupon()
+        .accounts({
+          owner: secondInvestorOwner.publicKey,
+          treasuryBonds: treasuryBonds,
+          couponSchedule: couponSchedule,
+          amortizationSchedule: amortizationSchedule,
+          investor: secondInvestor,
+          holding: secondInvestorHolding,
+          taxProfile: taxProfile,
+          taxRecord: secondInvestorTaxRecord,
+          senderTokens: vaultTokens,
+          recipientTokens: secondInvestorOwnerATA.publicKey,
+          taxAuthorityTokens: tokenAccount,
+          mintToken: mintToken.publicKey,
+          depositAccount: depositAccount.publicKey,
+          pdaAuth: pdaAuth,
+          treasuryVault: treasuryVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associateTokenProgram: associateTokenProgram,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([secondInvestorOwner])
+        .rpc();
+    } catch (error) {
+      errorCode = error.error.errorCode.code;
+    }
+    assert.equal(errorCode, "NoCouponDue");
+
+    // no tax is recorded without a coupon paid
+    assert.isNull(
+      await program.account.taxRecord.fetchNullable(secondInvestorTaxRecord)
+    );
+  });
+
+  it("Is repay principal!", async () => {
+    try {
+      const tx = await program.methods
+        .repayPrincipal()
+        .accounts({
+          owner: secondInvestorOwner.publicKey,
+          treasuryBonds: treasuryBonds,
+          amortizationSchedule: amortizationSchedule,
+          investor: secondInvestor,
+          holding: secondInvestorHolding,
+          senderTokens: vaultTokens,
+          recipientTokens: secondInvestorOwnerATA.publicKey,
+          mintToken: mintToken.publicKey,
+          depositAccount: depositAccount.publicKey,
+          pdaAuth: pdaAuth,
+          treasuryVault: treasuryVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: associateTokenProgram,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([secondInvestorOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      // fixed coupon bonds repay the face value at maturity
+      console.log(error);
+    }
+
+    try {
+      let result = await program.account.holding.fetch(secondInvestorHolding);
+      console.log("holding: ", result);
+
+      let result2 = await program.account.amortizationSchedule.fetch(
+        amortizationSchedule
+      );
+      console.log("amortization schedule: ", result2);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is declare maturity!", async () => {
+    await proposeAndApprove(
+      declareMaturityProposal,
+      2,
+      treasuryBonds,
+      Buffer.alloc(0)
+    );
+
+    await program.methods
+      .declareMaturity()
+      .accounts({
+        treasuryBondsConfigs: treasuryBondsConfigs,
+        proposal: declareMaturityProposal,
+        owner: treasuryBondsOwner.publicKey,
+        treasuryBonds: treasuryBonds,
+      })
+      .signers([treasuryBondsOwner])
+      .rpc();
+
+    let result = await program.account.treasuryBonds.fetch(treasuryBonds);
+    assert.isTrue(result.isMatured);
+
+    let result2 = await program.account.proposal.fetch(declareMaturityProposal);
+    assert.isTrue(result2.isExecuted);
   });
 
   it("Is redeem treasury bonds!", async () => {
@@ -514,14 +1837,20 @@ describe("treasury_bonds", () => {
         .accounts({
           owner: secondInvestorOwner.publicKey,
           treasuryBonds: treasuryBonds,
+          couponSchedule: couponSchedule,
+          amortizationSchedule: amortizationSchedule,
           investor: secondInvestor,
-          senderTokens: treasuryVaultATA.address,
+          holding: secondInvestorHolding,
+          senderTokens: vaultTokens,
           recipientTokens: secondInvestorOwnerATA.publicKey,
           mintToken: mintToken.publicKey,
+          bondMint: bondMint,
+          bondTokens: secondInvestorBondTokens,
           depositAccount: depositAccount.publicKey,
           pdaAuth: pdaAuth,
           treasuryVault: treasuryVault,
           tokenProgram: TOKEN_PROGRAM_ID,
+          bondTokenProgram: TOKEN_PROGRAM_ID,
           associateTokenProgram: associateTokenProgram,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
@@ -538,8 +1867,10 @@ describe("treasury_bonds", () => {
       );
       console.log("deposit account: ", result);
 
-      let result2 = await program.account.investor.fetch(secondInvestor);
-      console.log("investor: ", result2);
+      let result2 = await program.account.holding.fetch(
+        secondInvestorHolding
+      );
+      console.log("holding: ", result2);
 
       let result3 = await program.account.treasuryBonds.fetch(treasuryBonds);
       console.log("treasury bonds: ", result3);
@@ -547,4 +1878,342 @@ describe("treasury_bonds", () => {
       console.log(error);
     }
   });
+
+  it("Is zero coupon bond bought at a discount and redeemed at par!", async () => {
+    let bondIssuer = {
+      issuer: "Republic of Kenya",
+    };
+
+    let initParams = {
+      issuer: bondIssuer,
+      country: "KE",
+      issueNo: zeroCouponIssueNo,
+      typeOfBond: 3, // 3 - Zero coupon bonds, discounted at the yield of the tenor
+      tenor: 5, // years
+      couponRate: 0, // zero coupon bonds pay no coupon
+      totalAmountsOffered: 100, // USD
+      minimumBidAmount: 1, // USD
+      minimumTradeAmount: 0, // USD, zero defaults to the minimum bid amount
+      allowsEarlyRedemption: false, // redeem before maturity
+      dayCount: 2, // day-count convention i.e 1 - Actual/365, 2 - Actual/Actual (ICMA), 3 - 30/360
+      unitCostOfTreasuryBonds: 1, // unit cost of treasury bonds
+      decimals: 9, // token mint in smallest unit i.e 9 decimals
+      valueDate: new anchor.BN(1715731200), // 15-05-2024
+      redemptionDate: new anchor.BN(1873497600), // 15-05-2029
+      transferHook: false,
+      amortizationSchedule: [],
+    };
+
+    await proposeAndApprove(
+      registerZeroCouponProposal,
+      1,
+      zeroCouponTreasuryBonds,
+      program.coder.types.encode("RegisterTreasuryBondsParams", initParams)
+    );
+
+    await program.methods
+      .registerTreasuryBonds(initParams)
+      .accounts({
+        owner: treasuryBondsOwner.publicKey,
+        treasuryBondsConfigs: treasuryBondsConfigs,
+        proposal: registerZeroCouponProposal,
+        treasuryBonds: zeroCouponTreasuryBonds,
+        couponSchedule: zeroCouponCouponSchedule,
+        amortizationSchedule: zeroCouponAmortizationSchedule,
+        depositAccount: zeroCouponDepositAccount.publicKey,
+        pdaAuth: zeroCouponPdaAuth,
+        treasuryVault: zeroCouponTreasuryVault,
+        vaultTokens: zeroCouponVaultTokens,
+        mintToken: mintToken.publicKey,
+        bondMint: zeroCouponBondMint,
+        extraAccountMetaList: zeroCouponExtraAccountMetaList,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        bondTokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: associateTokenProgram,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([treasuryBondsOwner, zeroCouponDepositAccount])
+      .rpc();
+
+    // zero coupon bonds are quoted over the half-years left to redemption
+    const quote = await program.methods
+      .quotePriceYield({ cleanPrice: null, yieldRate: 1125 })
+      .accounts({
+        treasuryBonds: zeroCouponTreasuryBonds,
+        couponSchedule: zeroCouponCouponSchedule,
+        amortizationSchedule: zeroCouponAmortizationSchedule,
+      })
+      .view();
+    console.log("zero coupon price yield quote: ", quote);
+    assert.isAtLeast(quote.remainingPeriods, 1);
+    assert.isAtMost(quote.remainingPeriods, 10);
+    assert.equal(quote.accruedInterest, 0);
+    assert.equal(quote.cleanPrice, quote.dirtyPrice);
+    assert.isBelow(quote.cleanPrice, 10000);
+
+    // 5 year zero coupon bond discounted at 11.25% over the half-years left to redemption,
+    // bought after issue i.e above 10 / 1.05625 ^ 10 = 5.7854 USD
+    const faceValue = 10 * 10 ** 9;
+    const balanceBeforePurchase = (
+      await provider.connection.getTokenAccountBalance(
+        firstInvestorOwnerATA.publicKey
+      )
+    ).value.amount;
+
+    await program.methods
+      .buyTreasuryBonds({ amount: 10 })
+      .accounts({
+        owner: firstInvestorOwner.publicKey,
+        treasuryBonds: zeroCouponTreasuryBonds,
+        couponSchedule: zeroCouponCouponSchedule,
+        amortizationSchedule: zeroCouponAmortizationSchedule,
+        investor: firstInvestor,
+        holding: firstInvestorZeroCouponHolding,
+        senderTokens: firstInvestorOwnerATA.publicKey,
+        recipientTokens: zeroCouponVaultTokens,
+        mintToken: mintToken.publicKey,
+        bondMint: zeroCouponBondMint,
+        bondTokens: firstInvestorZeroCouponBondTokens,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        bondTokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: associateTokenProgram,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([firstInvestorOwner])
+      .rpc();
+
+    const balanceAfterPurchase = (
+      await provider.connection.getTokenAccountBalance(
+        firstInvestorOwnerATA.publicKey
+      )
+    ).value.amount;
+    const purchasePrice =
+      Number(balanceBeforePurchase) - Number(balanceAfterPurchase);
+    console.log("zero coupon purchase price: ", purchasePrice);
+    assert.isAbove(purchasePrice, 5785391862);
+    assert.isBelow(purchasePrice, faceValue);
+    // purchase price agrees with the price quote (2 decimals per 100 face value)
+    assert.approximately(
+      purchasePrice,
+      (quote.cleanPrice * faceValue) / 10000,
+      1000000
+    );
+
+    // treasurer funds the accretion to par ahead of redemption
+    await program.methods
+      .fundVault({ amount: 5 })
+      .accounts({
+        owner: payer.publicKey,
+        treasuryBondsConfigs: treasuryBondsConfigs,
+        treasuryBonds: zeroCouponTreasuryBonds,
+        senderTokens: tokenAccount,
+        vaultTokens: zeroCouponVaultTokens,
+        mintToken: mintToken.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    await proposeAndApprove(
+      declareZeroCouponMaturityProposal,
+      2,
+      zeroCouponTreasuryBonds,
+      Buffer.alloc(0)
+    );
+
+    await program.methods
+      .declareMaturity()
+      .accounts({
+        treasuryBondsConfigs: treasuryBondsConfigs,
+        proposal: declareZeroCouponMaturityProposal,
+        owner: treasuryBondsOwner.publicKey,
+        treasuryBonds: zeroCouponTreasuryBonds,
+      })
+      .signers([treasuryBondsOwner])
+      .rpc();
+
+    await program.methods
+      .redeemTreasuryBonds({ amount: 10 })
+      .accounts({
+        owner: firstInvestorOwner.publicKey,
+        treasuryBonds: zeroCouponTreasuryBonds,
+        couponSchedule: zeroCouponCouponSchedule,
+        amortizationSchedule: zeroCouponAmortizationSchedule,
+        investor: firstInvestor,
+        holding: firstInvestorZeroCouponHolding,
+        senderTokens: zeroCouponVaultTokens,
+        recipientTokens: firstInvestorOwnerATA.publicKey,
+        mintToken: mintToken.publicKey,
+        bondMint: zeroCouponBondMint,
+        bondTokens: firstInvestorZeroCouponBondTokens,
+        depositAccount: zeroCouponDepositAccount.publicKey,
+        pdaAuth: zeroCouponPdaAuth,
+        treasuryVault: zeroCouponTreasuryVault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        bondTokenProgram: TOKEN_PROGRAM_ID,
+        associateTokenProgram: associateTokenProgram,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([firstInvestorOwner])
+      .rpc();
+
+    // face value is paid in full at maturity
+    const balanceAfterRedemption = (
+      await provider.connection.getTokenAccountBalance(
+        firstInvestorOwnerATA.publicKey
+      )
+    ).value.amount;
+    assert.equal(
+      Number(balanceAfterRedemption) - Number(balanceAfterPurchase),
+      faceValue
+    );
+  });
+
+  it("Is withdraw vault with a single approval rejected!", async () => {
+    let errorCode = "";
+    try {
+      let initParams = {
+        amount: 5, // USD
+      };
+
+      // proposal is only signed off by its proposer
+      const tx = await program.methods
+        .createProposal({
+          action: 3, // withdraw vault
+          target: treasuryBonds,
+          payloadHash: Array.from(
+            createHash("sha256")
+              .update(
+                Buffer.concat([
+                  program.coder.types.encode("WithdrawVaultParams", initParams),
+                  tokenAccount.toBuffer(),
+                ])
+              )
+              .digest()
+          ),
+        })
+        .accounts({
+          owner: adminOwner.publicKey,
+          treasuryBondsConfigs: treasuryBondsConfigs,
+          proposal: singleApprovalProposal,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([adminOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+
+      await program.methods
+        .withdrawVault(initParams)
+        .accounts({
+          owner: payer.publicKey,
+          treasuryBondsConfigs: treasuryBondsConfigs,
+          proposal: singleApprovalProposal,
+          treasuryBonds: treasuryBonds,
+          senderTokens: vaultTokens,
+          recipientTokens: tokenAccount,
+          mintToken: mintToken.publicKey,
+          depositAccount: depositAccount.publicKey,
+          pdaAuth: pdaAuth,
+          treasuryVault: treasuryVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+    } catch (error) {
+      errorCode = error.error.errorCode.code;
+    }
+
+    assert.equal(errorCode, "ProposalNotApproved");
+  });
+
+  it("Is update tax profile!", async () => {
+    let initParams = {
+      withholdingTaxRate: 1000, // basis points i.e 10%
+    };
+
+    const updateTaxProfile = async (withholdingTaxRate: number) => {
+      await program.methods
+        .updateTaxProfile({ withholdingTaxRate: withholdingTaxRate })
+        .accounts({
+          treasuryBondsConfigs: treasuryBondsConfigs,
+          proposal: updateTaxProfileProposal,
+          treasuryBonds: treasuryBonds,
+          taxProfile: taxProfile,
+          taxAuthorityTokens: tokenAccount,
+          owner: treasuryBondsOwner.publicKey,
+        })
+        .signers([treasuryBondsOwner])
+        .rpc();
+    };
+
+    await proposeAndApprove(
+      updateTaxProfileProposal,
+      5,
+      treasuryBonds,
+      Buffer.concat([
+        program.coder.types.encode("UpdateTaxProfileParams", initParams),
+        tokenAccount.toBuffer(),
+      ])
+    );
+
+    // tax profile can only be changed as proposed
+    let errorCode = "";
+    try {
+      await updateTaxProfile(1200);
+    } catch (error) {
+      errorCode = error.error.errorCode.code;
+    }
+    assert.equal(errorCode, "ProposalMismatch");
+
+    await updateTaxProfile(initParams.withholdingTaxRate);
+
+    let result = await program.account.taxProfile.fetch(taxProfile);
+    assert.equal(result.withholdingTaxRate, 1000);
+
+    let result2 = await program.account.proposal.fetch(
+      updateTaxProfileProposal
+    );
+    assert.isTrue(result2.isExecuted);
+  });
+
+  it("Is transfer admin!", async () => {
+    try {
+      let transferAdminParams = {
+        newAdmin: treasuryBondsOwner.publicKey,
+      };
+
+      const tx = await program.methods
+        .transferAdmin(transferAdminParams)
+        .accounts({
+          owner: adminOwner.publicKey,
+          treasuryBondsConfigs: treasuryBondsConfigs,
+        })
+        .signers([adminOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    try {
+      const tx = await program.methods
+        .acceptAdmin()
+        .accounts({
+          owner: treasuryBondsOwner.publicKey,
+          treasuryBondsConfigs: treasuryBondsConfigs,
+        })
+        .signers([treasuryBondsOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    try {
+      let result = await program.account.treasuryBondsConfigs.fetch(
+        treasuryBondsConfigs
+      );
+      console.log("treasuryBondsConfigs: ", result);
+    } catch (error) {
+      console.log(error);
+    }
+  });
 });